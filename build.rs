@@ -0,0 +1,77 @@
+//! Build script - with the `bundled-help` feature, checks the help snapshot
+//! against the pinned openclaw version and copies it into OUT_DIR so
+//! src/cache.rs can embed it.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Snapshot used when CHITIN_HELP_SNAPSHOT isn't set
+const DEFAULT_SNAPSHOT: &str = "snapshot/help.json";
+
+/// File declaring `const OPENCLAW_VERSION`
+const VERSION_SOURCE: &str = "src/main.rs";
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=CHITIN_HELP_SNAPSHOT");
+
+    if env::var_os("CARGO_FEATURE_BUNDLED_HELP").is_none() {
+        return;
+    }
+
+    let snapshot = env::var_os("CHITIN_HELP_SNAPSHOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_SNAPSHOT));
+    println!("cargo:rerun-if-changed={}", snapshot.display());
+    println!("cargo:rerun-if-changed={}", VERSION_SOURCE);
+
+    let content = fs::read_to_string(&snapshot).unwrap_or_else(|e| {
+        panic!(
+            "bundled-help: cannot read help snapshot {}: {}\n\
+             Create it with the pinned openclaw version installed:\n  \
+             chitin cache warm && chitin cache export {}\n\
+             or point CHITIN_HELP_SNAPSHOT at an existing snapshot.",
+            snapshot.display(),
+            e,
+            DEFAULT_SNAPSHOT
+        )
+    });
+
+    let pinned = pinned_version();
+    let parsed: serde_json::Value = serde_json::from_str(&content).unwrap_or_else(|e| {
+        panic!(
+            "bundled-help: {} is not valid JSON: {}",
+            snapshot.display(),
+            e
+        )
+    });
+    let version = parsed["openclaw_version"].as_str().unwrap_or_default();
+    if version != pinned {
+        panic!(
+            "bundled-help: {} is for openclaw {:?}, but chitin is pinned to {}\n\
+             Re-export it with openclaw {} installed:\n  \
+             chitin cache warm && chitin cache export {}",
+            snapshot.display(),
+            version,
+            pinned,
+            pinned,
+            DEFAULT_SNAPSHOT
+        );
+    }
+
+    let out = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(out.join("help_snapshot.json"), content).expect("failed to write help snapshot");
+}
+
+/// Read the pinned openclaw version from `const OPENCLAW_VERSION` in src/main.rs
+fn pinned_version() -> String {
+    let source = fs::read_to_string(VERSION_SOURCE).expect("failed to read src/main.rs");
+    source
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix("const OPENCLAW_VERSION: &str = \"")?
+                .strip_suffix("\";")
+                .map(str::to_string)
+        })
+        .expect("const OPENCLAW_VERSION not found in src/main.rs")
+}
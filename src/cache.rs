@@ -3,10 +3,25 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::command_tree::{self, CommandNode};
+
+/// Cached help for a single command
+#[derive(Serialize, Deserialize, Clone)]
+struct CacheEntry {
+    /// Help text as printed by openclaw (rebranded on display)
+    help: String,
+    /// Command structure parsed from the help text
+    tree: CommandNode,
+    /// Timestamp when this entry was cached (Unix epoch seconds)
+    #[serde(default)]
+    timestamp: u64,
+}
+
 /// Cache file structure - stores help for main command and all subcommands
 #[derive(Serialize, Deserialize, Default)]
 struct CacheFile {
@@ -16,60 +31,121 @@ struct CacheFile {
     chitin_version: String,
     /// Timestamp when cache was created (Unix epoch seconds)
     timestamp: u64,
-    /// Help text for each command (empty string key = main help)
-    commands: HashMap<String, String>,
+    /// Help for each command (empty string key = main help)
+    commands: HashMap<String, CacheEntry>,
+}
+
+/// Help text found in the cache
+pub struct CachedHelp {
+    pub help: String,
+    /// Whether the entry is older than the refresh age and should be re-fetched
+    pub stale: bool,
+}
+
+/// Summary of the cache file contents, regardless of validity
+pub struct CacheInfo {
+    pub openclaw_version: String,
+    pub chitin_version: String,
+    /// Timestamp of the last write (Unix epoch seconds)
+    pub timestamp: u64,
+    /// Cached command paths with the time each was cached, sorted by path
+    pub entries: Vec<(String, u64)>,
+    /// Size of the cache file in bytes
+    pub size: u64,
 }
 
 /// Help cache manager
 pub struct HelpCache {
     cache_path: PathBuf,
+    /// Age in seconds after which cached help is refreshed in the background
+    max_age: u64,
+    /// Help bundled into the binary, consulted when the cache file has none
+    snapshot: Option<&'static CacheFile>,
+}
+
+/// Help snapshot embedded at build time with the `bundled-help` feature, in
+/// the cache file format (see `chitin cache export`)
+#[cfg(feature = "bundled-help")]
+const EMBEDDED_SNAPSHOT: Option<&str> = Some(include_str!(concat!(
+    env!("OUT_DIR"),
+    "/help_snapshot.json"
+)));
+#[cfg(not(feature = "bundled-help"))]
+const EMBEDDED_SNAPSHOT: Option<&str> = None;
+
+/// The embedded help snapshot, parsed once per process
+fn embedded_snapshot() -> Option<&'static CacheFile> {
+    static SNAPSHOT: std::sync::OnceLock<Option<CacheFile>> = std::sync::OnceLock::new();
+
+    SNAPSHOT
+        .get_or_init(|| serde_json::from_str(EMBEDDED_SNAPSHOT?).ok())
+        .as_ref()
 }
 
 impl HelpCache {
-    /// Create a new HelpCache instance
+    /// Create a new HelpCache instance using the configured location and TTL
     pub fn new() -> Result<Self> {
-        let cache_dir = Self::get_cache_dir()?;
-        fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
-
         Ok(Self {
-            cache_path: cache_dir.join("help_cache.json"),
+            cache_path: cache_dir()?.join("help_cache.json"),
+            max_age: crate::config::get().cache_ttl,
+            snapshot: embedded_snapshot(),
         })
     }
 
-    /// Get the cache directory path
-    fn get_cache_dir() -> Result<PathBuf> {
-        // Prefer ~/.chitin/cache
-        if let Some(home) = dirs::home_dir() {
-            return Ok(home.join(".chitin").join("cache"));
+    /// Create a HelpCache backed by a specific cache file
+    #[cfg(test)]
+    pub fn with_path(cache_path: PathBuf) -> Self {
+        Self {
+            cache_path,
+            max_age: crate::config::DEFAULT_CACHE_TTL,
+            snapshot: None,
         }
+    }
 
-        // Fallback to XDG cache
-        if let Some(cache) = dirs::cache_dir() {
-            return Ok(cache.join("chitin"));
+    /// Create a HelpCache backed by a specific cache file and help snapshot
+    #[cfg(test)]
+    fn with_snapshot(cache_path: PathBuf, snapshot: &str) -> Self {
+        let snapshot: CacheFile = serde_json::from_str(snapshot).unwrap();
+        Self {
+            snapshot: Some(Box::leak(Box::new(snapshot))),
+            ..Self::with_path(cache_path)
         }
+    }
 
-        anyhow::bail!("Cannot determine cache directory")
+    /// The bundled snapshot, if it was taken from `openclaw_version`
+    fn snapshot_for(&self, openclaw_version: &str) -> Option<&'static CacheFile> {
+        self.snapshot
+            .filter(|snapshot| snapshot.openclaw_version == openclaw_version)
     }
 
-    /// Load the cache file, returning None if invalid or expired
-    fn load_cache(
-        &self,
-        expected_openclaw_version: &str,
-        expected_chitin_version: &str,
-    ) -> Result<Option<CacheFile>> {
+    /// Get the cache file path
+    pub fn path(&self) -> &Path {
+        &self.cache_path
+    }
+
+    /// Read the cache file without validating it, returning None if missing or invalid
+    fn read_cache(&self) -> Result<Option<CacheFile>> {
         if !self.cache_path.exists() {
             return Ok(None);
         }
 
         let content = fs::read_to_string(&self.cache_path).context("Failed to read cache file")?;
 
-        let cache: CacheFile = match serde_json::from_str(&content) {
-            Ok(c) => c,
-            Err(_) => {
-                // Invalid cache, delete it
-                let _ = fs::remove_file(&self.cache_path);
-                return Ok(None);
-            }
+        // Invalid cache (e.g. an older format) is treated as empty and replaced on
+        // the next write; deleting it here could race with a concurrent writer
+        Ok(serde_json::from_str(&content).ok())
+    }
+
+    /// Load the cache file, returning None if invalid or for other versions.
+    /// Expired entries are still returned so they can be served while refreshing.
+    fn load_cache(
+        &self,
+        expected_openclaw_version: &str,
+        expected_chitin_version: &str,
+    ) -> Result<Option<CacheFile>> {
+        let cache = match self.read_cache()? {
+            Some(c) => c,
+            None => return Ok(None),
         };
 
         // Check version match (both openclaw and chitin versions must match)
@@ -80,50 +156,138 @@ impl HelpCache {
             return Ok(None);
         }
 
-        // Check cache age (invalidate after 24 hours)
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::ZERO)
-            .as_secs();
-
-        let max_age = 24 * 60 * 60; // 24 hours
-        if now - cache.timestamp > max_age {
-            return Ok(None);
-        }
-
         Ok(Some(cache))
     }
 
-    /// Save the cache file
-    fn save_cache(&self, cache: &CacheFile) -> Result<()> {
-        let content = serde_json::to_string_pretty(cache).context("Failed to serialize cache")?;
-        fs::write(&self.cache_path, content).context("Failed to write cache file")?;
-        Ok(())
+    /// Take the exclusive advisory lock that serializes cache updates across
+    /// processes. Released when the returned file is dropped.
+    fn lock(&self) -> Result<File> {
+        let lock_path = self.cache_path.with_extension("json.lock");
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .context("Failed to open cache lock file")?;
+        file.lock().context("Failed to lock cache")?;
+        Ok(file)
     }
 
-    /// Get cached help for main command if valid
-    pub fn get_cached_help(
-        &self,
-        openclaw_version: &str,
-        chitin_version: &str,
-    ) -> Result<Option<String>> {
-        self.get_cached_subcommand_help("", openclaw_version, chitin_version)
+    /// Save the cache file atomically: write a temporary file next to it and
+    /// rename it into place, so readers never see a partial file
+    fn save_cache(&self, cache: &CacheFile) -> Result<()> {
+        let content = serde_json::to_string_pretty(cache).context("Failed to serialize cache")?;
+        let tmp_path = self
+            .cache_path
+            .with_extension(format!("json.{}.tmp", std::process::id()));
+
+        let result = File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(content.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&tmp_path, &self.cache_path));
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        result.context("Failed to write cache file")
     }
 
-    /// Get cached help for a subcommand if valid
+    /// Look up cached help for a subcommand, reporting whether it is stale
     /// Use empty string for main help
-    pub fn get_cached_subcommand_help(
+    pub fn lookup_help(
         &self,
         subcommand: &str,
         openclaw_version: &str,
         chitin_version: &str,
-    ) -> Result<Option<String>> {
+    ) -> Result<Option<CachedHelp>> {
+        let cached = self
+            .load_cache(openclaw_version, chitin_version)?
+            .and_then(|cache| cache.commands.get(subcommand).cloned())
+            .map(|e| CachedHelp {
+                help: e.help,
+                stale: now().saturating_sub(e.timestamp) > self.max_age,
+            });
+
+        // The bundled snapshot never expires; it only changes with chitin
+        Ok(cached.or_else(|| {
+            let entry = self
+                .snapshot_for(openclaw_version)?
+                .commands
+                .get(subcommand)?;
+            Some(CachedHelp {
+                help: entry.help.clone(),
+                stale: false,
+            })
+        }))
+    }
+
+    /// Look up cached help for a subcommand whatever openclaw and chitin
+    /// versions it was cached for, returning it with the openclaw version. For
+    /// when openclaw can't be run to fetch current help.
+    pub fn lookup_help_any_version(&self, subcommand: &str) -> Result<Option<(String, String)>> {
+        let cache = self.read_cache()?;
+        Ok([cache.as_ref(), self.snapshot]
+            .into_iter()
+            .flatten()
+            .find_map(|cache| {
+                let entry = cache.commands.get(subcommand)?;
+                Some((entry.help.clone(), cache.openclaw_version.clone()))
+            }))
+    }
+
+    /// Command paths whose cached help is older than the refresh age
+    pub fn stale_paths(&self, openclaw_version: &str, chitin_version: &str) -> Result<Vec<String>> {
         let cache = match self.load_cache(openclaw_version, chitin_version)? {
             Some(c) => c,
-            None => return Ok(None),
+            None => return Ok(Vec::new()),
         };
 
-        Ok(cache.commands.get(subcommand).cloned())
+        let mut paths: Vec<String> = cache
+            .commands
+            .iter()
+            .filter(|(_, e)| now().saturating_sub(e.timestamp) > self.max_age)
+            .map(|(path, _)| path.clone())
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Get the full command tree, with every cached subcommand merged into the
+    /// main command's tree. Returns None if the main help is not cached.
+    pub fn get_command_tree(
+        &self,
+        openclaw_version: &str,
+        chitin_version: &str,
+    ) -> Result<Option<CommandNode>> {
+        let cache = self.load_cache(openclaw_version, chitin_version)?;
+        Ok(cache
+            .as_ref()
+            .and_then(full_tree)
+            .or_else(|| full_tree(self.snapshot_for(openclaw_version)?)))
+    }
+
+    /// Get the full cached command tree whatever versions it was cached for
+    pub fn get_command_tree_any_version(&self) -> Result<Option<CommandNode>> {
+        let cache = self.read_cache()?;
+        Ok(cache
+            .as_ref()
+            .and_then(full_tree)
+            .or_else(|| full_tree(self.snapshot?)))
+    }
+
+    /// Write the cache for `openclaw_version` as a help snapshot, the input for
+    /// builds with the `bundled-help` feature
+    fn export(&self, path: &Path, openclaw_version: &str, chitin_version: &str) -> Result<usize> {
+        let Some(cache) = self.load_cache(openclaw_version, chitin_version)? else {
+            anyhow::bail!(
+                "No help cached for openclaw {}; run 'chitin cache warm' first",
+                openclaw_version
+            );
+        };
+        let content = serde_json::to_string(&cache).context("Failed to serialize cache")?;
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(cache.commands.len())
     }
 
     /// Save help text for main command to cache
@@ -145,35 +309,103 @@ impl HelpCache {
         openclaw_version: &str,
         chitin_version: &str,
     ) -> Result<()> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::ZERO)
-            .as_secs();
+        self.save_all_help(
+            &[(subcommand.to_string(), help_text.to_string())],
+            openclaw_version,
+            chitin_version,
+        )
+    }
 
-        // Load existing cache or create new one
-        let mut cache = self
-            .load_cache(openclaw_version, chitin_version)?
-            .unwrap_or_else(|| CacheFile {
-                openclaw_version: openclaw_version.to_string(),
-                chitin_version: chitin_version.to_string(),
-                timestamp,
-                commands: HashMap::new(),
-            });
+    /// Save help text for several commands in a single cache update
+    pub fn save_all_help(
+        &self,
+        entries: &[(String, String)],
+        openclaw_version: &str,
+        chitin_version: &str,
+    ) -> Result<()> {
+        self.update(entries, openclaw_version, chitin_version, false)
+    }
+
+    /// Replace the whole cache with help for `entries` in a single update, so
+    /// the previous contents survive until the new ones are complete
+    pub fn replace_all_help(
+        &self,
+        entries: &[(String, String)],
+        openclaw_version: &str,
+        chitin_version: &str,
+    ) -> Result<()> {
+        self.update(entries, openclaw_version, chitin_version, true)
+    }
+
+    fn update(
+        &self,
+        entries: &[(String, String)],
+        openclaw_version: &str,
+        chitin_version: &str,
+        replace: bool,
+    ) -> Result<()> {
+        let timestamp = now();
+
+        // Hold the lock across read-modify-write so concurrent updates merge
+        // instead of overwriting each other's entries
+        let _lock = self.lock()?;
 
-        // Update timestamp and add/update the command
+        // Load existing cache (unless replacing it) or create new one
+        let existing = match replace {
+            true => None,
+            false => self.load_cache(openclaw_version, chitin_version)?,
+        };
+        let mut cache = existing.unwrap_or_else(|| CacheFile {
+            openclaw_version: openclaw_version.to_string(),
+            chitin_version: chitin_version.to_string(),
+            timestamp,
+            commands: HashMap::new(),
+        });
+
+        // Update timestamp and add/update the commands
         cache.timestamp = timestamp;
         cache.openclaw_version = openclaw_version.to_string();
         cache.chitin_version = chitin_version.to_string();
-        cache
-            .commands
-            .insert(subcommand.to_string(), help_text.to_string());
+        for (subcommand, help_text) in entries {
+            cache.commands.insert(
+                subcommand.clone(),
+                CacheEntry {
+                    help: help_text.clone(),
+                    tree: command_tree::parse_help(help_text),
+                    timestamp,
+                },
+            );
+        }
 
         self.save_cache(&cache)
     }
 
+    /// Describe the cache file contents, even if expired or for another version
+    pub fn info(&self) -> Result<Option<CacheInfo>> {
+        let cache = match self.read_cache()? {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        let mut entries: Vec<(String, u64)> = cache
+            .commands
+            .iter()
+            .map(|(path, entry)| (path.clone(), entry.timestamp))
+            .collect();
+        entries.sort();
+
+        Ok(Some(CacheInfo {
+            openclaw_version: cache.openclaw_version,
+            chitin_version: cache.chitin_version,
+            timestamp: cache.timestamp,
+            entries,
+            size: fs::metadata(&self.cache_path).map(|m| m.len()).unwrap_or(0),
+        }))
+    }
+
     /// Clear the cache
-    #[allow(dead_code)]
     pub fn clear(&self) -> Result<()> {
+        let _lock = self.lock()?;
         if self.cache_path.exists() {
             // Ignore errors if file was already deleted (race condition in tests)
             let _ = fs::remove_file(&self.cache_path);
@@ -182,35 +414,249 @@ impl HelpCache {
     }
 }
 
+/// Main command tree with every cached subcommand's tree merged in
+fn full_tree(cache: &CacheFile) -> Option<CommandNode> {
+    let mut root = cache.commands.get("")?.tree.clone();
+    merge_subtrees(&mut root, "", &cache.commands);
+    Some(root)
+}
+
+/// Describe the bundled help snapshot, if chitin was built with one
+fn print_snapshot(cache: &HelpCache) {
+    if let Some(snapshot) = cache.snapshot {
+        println!(
+            "Bundled snapshot: openclaw {}, {} commands",
+            snapshot.openclaw_version,
+            snapshot.commands.len()
+        );
+    }
+}
+
+/// Get the configured cache directory, creating it if needed
+pub fn cache_dir() -> Result<PathBuf> {
+    let cache_dir = match &crate::config::get().cache_dir {
+        Some(dir) => dir.clone(),
+        None => default_cache_dir()?,
+    };
+    fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
+    Ok(cache_dir)
+}
+
+/// Get the default cache directory path
+fn default_cache_dir() -> Result<PathBuf> {
+    // Prefer ~/.chitin/cache
+    if let Some(home) = dirs::home_dir() {
+        return Ok(home.join(".chitin").join("cache"));
+    }
+
+    // Fallback to XDG cache
+    if let Some(cache) = dirs::cache_dir() {
+        return Ok(cache.join("chitin"));
+    }
+
+    anyhow::bail!("Cannot determine cache directory")
+}
+
+/// Current time as Unix epoch seconds
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+/// Format an age in seconds as a short human-readable string
+pub fn format_age(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s ago", secs),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// Handle `chitin cache <show|path|clear|warm|stats>`
+pub fn command(args: &[String]) -> Result<()> {
+    let cache = HelpCache::new()?;
+    let openclaw_version = crate::openclaw::version();
+
+    match args.first().map(|s| s.as_str()) {
+        Some("path") => {
+            println!("{}", cache.path().display());
+        }
+        Some("show") => {
+            let Some(info) = cache.info()? else {
+                println!("Help cache is empty ({})", cache.path().display());
+                return Ok(());
+            };
+
+            println!("Cache: {}", cache.path().display());
+            println!(
+                "Written by chitin {} for openclaw {}, {}",
+                info.chitin_version,
+                info.openclaw_version,
+                format_age(now().saturating_sub(info.timestamp))
+            );
+            println!();
+
+            let width = info.entries.iter().map(|(p, _)| p.len()).max().unwrap_or(0);
+            for (path, timestamp) in &info.entries {
+                let name = if path.is_empty() { "(main)" } else { path };
+                let age = if *timestamp == 0 {
+                    "unknown".to_string()
+                } else {
+                    format_age(now().saturating_sub(*timestamp))
+                };
+                println!("  {:width$}  {}", name, age, width = width.max(6));
+            }
+        }
+        Some("stats") => {
+            let Some(info) = cache.info()? else {
+                println!("Help cache is empty ({})", cache.path().display());
+                print_snapshot(&cache);
+                return Ok(());
+            };
+
+            let current = cache
+                .load_cache(openclaw_version, crate::CHITIN_VERSION)?
+                .is_some();
+            let stale = cache.stale_paths(openclaw_version, crate::CHITIN_VERSION)?;
+            let oldest = info.entries.iter().map(|(_, t)| *t).min().unwrap_or(0);
+
+            println!("Path:             {}", cache.path().display());
+            println!("Size:             {} bytes", info.size);
+            println!("Commands:         {}", info.entries.len());
+            println!("openclaw version: {}", info.openclaw_version);
+            println!("chitin version:   {}", info.chitin_version);
+            println!(
+                "Last written:     {}",
+                format_age(now().saturating_sub(info.timestamp))
+            );
+            if oldest > 0 {
+                println!(
+                    "Oldest entry:     {}",
+                    format_age(now().saturating_sub(oldest))
+                );
+            }
+            let status = if !current {
+                "outdated (version mismatch)".to_string()
+            } else if stale.is_empty() {
+                "fresh".to_string()
+            } else {
+                format!(
+                    "{} stale entries (refreshed in the background)",
+                    stale.len()
+                )
+            };
+            println!("Status:           {}", status);
+            print_snapshot(&cache);
+        }
+        Some("export") => {
+            let path = args.get(1).context("Usage: chitin cache export <file>")?;
+            let count = cache.export(Path::new(path), openclaw_version, crate::CHITIN_VERSION)?;
+            println!(
+                "Exported help for {} command(s) (openclaw {}) to {}",
+                count, openclaw_version, path
+            );
+        }
+        Some("clear") => {
+            cache.clear()?;
+            crate::openclaw::forget_location();
+            println!("Cleared help cache ({})", cache.path().display());
+        }
+        Some("warm") => {
+            let jobs = match args.get(1).map(|s| s.as_str()) {
+                Some("--jobs" | "-j") => args
+                    .get(2)
+                    .and_then(|n| n.parse().ok())
+                    .context("--jobs requires a number")?,
+                _ => crate::prefetch::DEFAULT_JOBS,
+            };
+
+            // The fetched tree replaces the cache in one update at the end, so
+            // a failed or interrupted warm keeps the existing cache
+            let started = std::time::Instant::now();
+            let report = crate::prefetch::prefetch_all(&cache, openclaw_version, jobs)?;
+            for (path, error) in &report.failed {
+                eprintln!("Failed to cache help for '{}': {}", path, error);
+            }
+            println!(
+                "Cached help for {} command(s) in {:.1}s",
+                report.cached,
+                started.elapsed().as_secs_f64()
+            );
+        }
+        _ => anyhow::bail!(
+            "Usage: chitin cache <show|path|clear|warm [--jobs N]|stats|export <file>>"
+        ),
+    }
+
+    Ok(())
+}
+
+/// Replace the listed subcommands of `node` with their own cached trees, recursively
+fn merge_subtrees(node: &mut CommandNode, path: &str, commands: &HashMap<String, CacheEntry>) {
+    for child in node.subcommands.iter_mut() {
+        let child_path = if path.is_empty() {
+            child.name.clone()
+        } else {
+            format!("{} {}", path, child.name)
+        };
+
+        if let Some(entry) = commands.get(&child_path) {
+            let listed = std::mem::take(child);
+            *child = entry.tree.clone();
+            child.name = listed.name;
+            child.aliases = listed.aliases;
+            if child.description.is_empty() {
+                child.description = listed.description;
+            }
+        }
+        merge_subtrees(child, &child_path, commands);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Cached help text for `subcommand`, ignoring staleness
+    fn help(cache: &HelpCache, subcommand: &str, openclaw: &str, chitin: &str) -> Option<String> {
+        cache
+            .lookup_help(subcommand, openclaw, chitin)
+            .unwrap()
+            .map(|c| c.help)
+    }
+
     #[test]
     fn test_cache_roundtrip() {
-        let cache = HelpCache::new().unwrap();
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-roundtrip-{}.json", std::process::id())),
+        );
         cache.clear().unwrap();
 
         // No cache initially
-        assert!(cache.get_cached_help("1.0.0", "0.1.0").unwrap().is_none());
+        assert!(help(&cache, "", "1.0.0", "0.1.0").is_none());
 
         // Save and retrieve main help
         cache.save_help("Test help text", "1.0.0", "0.1.0").unwrap();
-        let cached = cache.get_cached_help("1.0.0", "0.1.0").unwrap();
+        let cached = help(&cache, "", "1.0.0", "0.1.0");
         assert_eq!(cached, Some("Test help text".to_string()));
 
         // Wrong openclaw version returns None
-        assert!(cache.get_cached_help("2.0.0", "0.1.0").unwrap().is_none());
+        assert!(help(&cache, "", "2.0.0", "0.1.0").is_none());
 
         // Wrong chitin version returns None
-        assert!(cache.get_cached_help("1.0.0", "0.2.0").unwrap().is_none());
+        assert!(help(&cache, "", "1.0.0", "0.2.0").is_none());
 
         cache.clear().unwrap();
     }
 
     #[test]
     fn test_subcommand_cache() {
-        let cache = HelpCache::new().unwrap();
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-subcommand-{}.json", std::process::id())),
+        );
         cache.clear().unwrap();
 
         // Save main and subcommand help
@@ -224,29 +670,324 @@ mod tests {
 
         // Retrieve each
         assert_eq!(
-            cache.get_cached_help("1.0.0", "0.1.0").unwrap(),
+            help(&cache, "", "1.0.0", "0.1.0"),
             Some("Main help".to_string())
         );
         assert_eq!(
-            cache
-                .get_cached_subcommand_help("gateway", "1.0.0", "0.1.0")
-                .unwrap(),
+            help(&cache, "gateway", "1.0.0", "0.1.0"),
             Some("Gateway help".to_string())
         );
         assert_eq!(
-            cache
-                .get_cached_subcommand_help("agent", "1.0.0", "0.1.0")
-                .unwrap(),
+            help(&cache, "agent", "1.0.0", "0.1.0"),
             Some("Agent help".to_string())
         );
 
+        // Parsed structure is stored alongside the text
+        let stored = cache.load_cache("1.0.0", "0.1.0").unwrap().unwrap();
+        assert!(stored.commands["gateway"].tree.options.is_empty());
+
         // Non-existent subcommand returns None
+        assert!(help(&cache, "nonexistent", "1.0.0", "0.1.0").is_none());
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_stale_entries_are_served() {
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-stale-{}.json", std::process::id())),
+        );
+        cache.clear().unwrap();
+
+        cache.save_help("Main help", "1.0.0", "0.1.0").unwrap();
+        cache
+            .save_subcommand_help("gateway", "Gateway help", "1.0.0", "0.1.0")
+            .unwrap();
+        assert!(cache.stale_paths("1.0.0", "0.1.0").unwrap().is_empty());
+
+        // Age the gateway entry past the refresh age
+        let mut file = cache.read_cache().unwrap().unwrap();
+        file.commands.get_mut("gateway").unwrap().timestamp =
+            now() - crate::config::DEFAULT_CACHE_TTL - 1;
+        cache.save_cache(&file).unwrap();
+
+        let main = cache.lookup_help("", "1.0.0", "0.1.0").unwrap().unwrap();
+        assert!(!main.stale);
+        let gateway = cache
+            .lookup_help("gateway", "1.0.0", "0.1.0")
+            .unwrap()
+            .unwrap();
+        assert_eq!(gateway.help, "Gateway help");
+        assert!(gateway.stale);
+        assert_eq!(
+            cache.stale_paths("1.0.0", "0.1.0").unwrap(),
+            vec!["gateway"]
+        );
+
+        // A version change still invalidates everything
+        assert!(cache.lookup_help("", "2.0.0", "0.1.0").unwrap().is_none());
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_lookup_any_version() {
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-offline-{}.json", std::process::id())),
+        );
+        cache.clear().unwrap();
+        assert!(cache.lookup_help_any_version("").unwrap().is_none());
+
+        cache
+            .save_help(
+                "Usage: openclaw [options]\n\nCommands:\n  gateway  Run the gateway\n",
+                "1.0.0",
+                "0.1.0",
+            )
+            .unwrap();
+        cache
+            .save_subcommand_help("gateway", "Gateway help", "1.0.0", "0.1.0")
+            .unwrap();
+
+        // Served for other versions, along with the version it came from
+        assert!(
+            cache
+                .lookup_help("gateway", "2.0.0", "0.2.0")
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            cache.lookup_help_any_version("gateway").unwrap(),
+            Some(("Gateway help".to_string(), "1.0.0".to_string()))
+        );
+        assert!(cache.lookup_help_any_version("agent").unwrap().is_none());
+
+        let tree = cache.get_command_tree_any_version().unwrap().unwrap();
+        assert_eq!(
+            tree.command_path(&["gateway".to_string(), "--help".to_string()]),
+            vec!["gateway"]
+        );
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_fallback() {
+        let dir = std::env::temp_dir().join(format!("chitin-snapshot-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // Export a warmed cache as a snapshot
+        let warmed = HelpCache::with_path(dir.join("warmed.json"));
+        assert!(
+            warmed
+                .export(&dir.join("help.json"), "1.0.0", "0.1.0")
+                .is_err()
+        );
+        warmed
+            .save_help(
+                "Usage: openclaw [options]\n\nCommands:\n  gateway  Run the gateway\n",
+                "1.0.0",
+                "0.1.0",
+            )
+            .unwrap();
+        warmed
+            .save_subcommand_help("gateway", "Gateway help", "1.0.0", "0.1.0")
+            .unwrap();
+        let count = warmed
+            .export(&dir.join("help.json"), "1.0.0", "0.1.0")
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // An empty cache falls back to it, for the snapshot's openclaw version only
+        let snapshot = fs::read_to_string(dir.join("help.json")).unwrap();
+        let cache = HelpCache::with_snapshot(dir.join("empty.json"), &snapshot);
+        let help = cache
+            .lookup_help("gateway", "1.0.0", "0.2.0")
+            .unwrap()
+            .unwrap();
+        assert_eq!(help.help, "Gateway help");
+        assert!(!help.stale);
+        assert!(cache.get_command_tree("1.0.0", "0.2.0").unwrap().is_some());
         assert!(
             cache
-                .get_cached_subcommand_help("nonexistent", "1.0.0", "0.1.0")
+                .lookup_help("gateway", "2.0.0", "0.2.0")
                 .unwrap()
                 .is_none()
         );
+        assert!(cache.get_command_tree("2.0.0", "0.2.0").unwrap().is_none());
+        assert_eq!(
+            cache.lookup_help_any_version("gateway").unwrap(),
+            Some(("Gateway help".to_string(), "1.0.0".to_string()))
+        );
+        assert!(cache.get_command_tree_any_version().unwrap().is_some());
+
+        // The on-disk cache takes precedence
+        cache
+            .save_subcommand_help("gateway", "Newer gateway help", "1.0.0", "0.2.0")
+            .unwrap();
+        let help = cache
+            .lookup_help("gateway", "1.0.0", "0.2.0")
+            .unwrap()
+            .unwrap();
+        assert_eq!(help.help, "Newer gateway help");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    /// The snapshot embedded with `bundled-help` must match the pinned version
+    #[cfg(feature = "bundled-help")]
+    #[test]
+    fn test_embedded_snapshot() {
+        let snapshot = embedded_snapshot().expect("embedded help snapshot is invalid");
+        assert_eq!(snapshot.openclaw_version, crate::OPENCLAW_VERSION);
+        assert!(snapshot.commands.contains_key(""));
+    }
+
+    #[test]
+    fn test_replace_all_help() {
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-replace-{}.json", std::process::id())),
+        );
+        cache.clear().unwrap();
+        cache
+            .save_all_help(
+                &[
+                    ("".to_string(), "Main help".to_string()),
+                    ("removed".to_string(), "Removed help".to_string()),
+                ],
+                "1.0.0",
+                "0.1.0",
+            )
+            .unwrap();
+
+        cache
+            .replace_all_help(
+                &[("".to_string(), "New main help".to_string())],
+                "1.0.0",
+                "0.1.0",
+            )
+            .unwrap();
+        let main = cache.lookup_help("", "1.0.0", "0.1.0").unwrap().unwrap();
+        assert_eq!(main.help, "New main help");
+        assert!(
+            cache
+                .lookup_help("removed", "1.0.0", "0.1.0")
+                .unwrap()
+                .is_none()
+        );
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_saves_merge() {
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-concurrent-{}.json", std::process::id())),
+        );
+        cache.clear().unwrap();
+
+        std::thread::scope(|scope| {
+            for writer in 0..8 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for n in 0..5 {
+                        let path = format!("cmd{} sub{}", writer, n);
+                        cache
+                            .save_subcommand_help(&path, "help", "1.0.0", "0.1.0")
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        // Every writer's entries survive and no temporary files are left behind
+        let info = cache.info().unwrap().unwrap();
+        assert_eq!(info.entries.len(), 40);
+        let dir = cache.path().parent().unwrap();
+        let prefix = cache
+            .path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let leftovers = fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                name.starts_with(&prefix) && name.ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_cache_info() {
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-info-{}.json", std::process::id())),
+        );
+        cache.clear().unwrap();
+        assert!(cache.info().unwrap().is_none());
+
+        cache.save_help("Main help", "1.0.0", "0.1.0").unwrap();
+        cache
+            .save_subcommand_help("channels login", "Login help", "1.0.0", "0.1.0")
+            .unwrap();
+
+        // Info is available regardless of the expected versions
+        let info = cache.info().unwrap().unwrap();
+        assert_eq!(info.openclaw_version, "1.0.0");
+        assert_eq!(info.chitin_version, "0.1.0");
+        assert_eq!(info.entries.len(), 2);
+        assert_eq!(info.entries[0].0, "");
+        assert_eq!(info.entries[1].0, "channels login");
+        assert!(info.entries[1].1 > 0);
+        assert!(info.size > 0);
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(5), "5s ago");
+        assert_eq!(format_age(120), "2m ago");
+        assert_eq!(format_age(7200), "2h ago");
+        assert_eq!(format_age(3 * 86400), "3d ago");
+    }
+
+    #[test]
+    fn test_command_tree() {
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-tree-{}.json", std::process::id())),
+        );
+        cache.clear().unwrap();
+
+        // No main help means no tree
+        cache
+            .save_subcommand_help("gateway", "Usage: openclaw gateway", "1.0.0", "0.1.0")
+            .unwrap();
+        assert!(cache.get_command_tree("1.0.0", "0.1.0").unwrap().is_none());
+
+        let main_help = "Usage: openclaw [options] [command]\n\nCommands:\n  gateway|gw  Run the gateway\n  agent       Talk to an agent\n";
+        let gateway_help =
+            "Usage: openclaw gateway [options]\n\nOptions:\n  --port <port>  Gateway port\n";
+        cache.save_help(main_help, "1.0.0", "0.1.0").unwrap();
+        cache
+            .save_subcommand_help("gateway", gateway_help, "1.0.0", "0.1.0")
+            .unwrap();
+
+        let tree = cache.get_command_tree("1.0.0", "0.1.0").unwrap().unwrap();
+        let gateway = tree.find_subcommand("gw").unwrap();
+        assert_eq!(gateway.name, "gateway");
+        assert_eq!(gateway.description, "Run the gateway");
+        assert!(gateway.find_option("--port").unwrap().value_required);
+
+        // Subcommands without cached help keep their listing
+        let agent = tree.find_subcommand("agent").unwrap();
+        assert_eq!(agent.description, "Talk to an agent");
+        assert!(agent.options.is_empty());
 
         cache.clear().unwrap();
     }
@@ -0,0 +1,291 @@
+//! Child processes chitin has to wait on (e.g. to capture help output), with
+//! signals and exit status passed through as if openclaw had run directly.
+
+use std::io::{self, Write};
+use std::process::{Command, ExitStatus, Output, Stdio};
+
+/// Run `command` to completion, capturing stdout and stderr. On Unix the child
+/// runs in its own process group, and SIGINT, SIGTERM and SIGHUP received by
+/// chitin meanwhile, even while it starts, are forwarded to that group. Several
+/// threads may capture at once; each signal reaches every running child.
+pub fn output(command: &mut Command) -> io::Result<Output> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        command.process_group(0);
+        let mut forwarding = unix::Forwarding::install();
+        let child = command.spawn()?;
+        forwarding.track(child.id());
+        child.wait_with_output()
+    }
+
+    #[cfg(not(unix))]
+    command.output()
+}
+
+/// Exit code a shell would report for `status`: the child's code, or 128+N
+/// if it was killed by signal N
+fn code(status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    status.code().unwrap_or(1)
+}
+
+/// Exit with the child's status. A child killed by a signal has the same
+/// signal re-raised on chitin, so callers see the real cause.
+pub fn exit(status: ExitStatus) -> ! {
+    let _ = io::stdout().flush();
+    let _ = io::stderr().flush();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            // SAFETY: restoring the default disposition and raising a signal
+            // have no memory-safety requirements
+            unsafe {
+                libc::signal(signal, libc::SIG_DFL);
+                libc::raise(signal);
+            }
+        }
+    }
+
+    // Signals whose default action doesn't terminate end up here
+    std::process::exit(code(status));
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+    /// Signals forwarded to the children's process groups
+    const FORWARDED: [libc::c_int; 3] = [libc::SIGINT, libc::SIGTERM, libc::SIGHUP];
+
+    /// Most children tracked at once; `cache warm` runs a few in parallel
+    const MAX_CHILDREN: usize = 32;
+
+    /// Process groups of the running children, 0 for a free slot
+    static CHILD_GROUPS: [AtomicI32; MAX_CHILDREN] = [const { AtomicI32::new(0) }; MAX_CHILDREN];
+
+    /// Last signal received while the handlers were installed, or 0
+    static RECEIVED: AtomicI32 = AtomicI32::new(0);
+
+    /// Whether a received signal reached at least one child
+    static DELIVERED: AtomicBool = AtomicBool::new(false);
+
+    /// Handlers replaced by the first `Forwarding` and how many are alive
+    static INSTALLED: Mutex<(usize, Vec<(libc::c_int, libc::sigaction)>)> =
+        Mutex::new((0, Vec::new()));
+
+    extern "C" fn forward(signal: libc::c_int) {
+        // Children tracked after this point pick the signal up in `track`
+        RECEIVED.store(signal, Ordering::SeqCst);
+        for slot in &CHILD_GROUPS {
+            let group = slot.load(Ordering::SeqCst);
+            if group > 0 {
+                // SAFETY: kill is async-signal-safe
+                unsafe {
+                    libc::kill(-group, signal);
+                }
+                DELIVERED.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Forwarding for one child. The handlers are installed while any
+    /// `Forwarding` is alive, and the previous ones restored after the last.
+    pub struct Forwarding {
+        slot: Option<usize>,
+    }
+
+    impl Forwarding {
+        pub fn install() -> Self {
+            let mut installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+            if installed.0 == 0 {
+                RECEIVED.store(0, Ordering::SeqCst);
+                DELIVERED.store(false, Ordering::SeqCst);
+                installed.1 = FORWARDED
+                    .into_iter()
+                    .filter_map(|signal| {
+                        // SAFETY: sigaction structs are plain data, and `forward`
+                        // only calls async-signal-safe functions
+                        unsafe {
+                            let mut action: libc::sigaction = std::mem::zeroed();
+                            action.sa_sigaction = forward as *const () as libc::sighandler_t;
+                            action.sa_flags = libc::SA_RESTART;
+                            libc::sigemptyset(&mut action.sa_mask);
+
+                            let mut old: libc::sigaction = std::mem::zeroed();
+                            (libc::sigaction(signal, &action, &mut old) == 0)
+                                .then_some((signal, old))
+                        }
+                    })
+                    .collect();
+            }
+            installed.0 += 1;
+            Self { slot: None }
+        }
+
+        /// Start forwarding to the group led by `pid`, delivering any signal
+        /// that arrived while it was being spawned
+        pub fn track(&mut self, pid: u32) {
+            let group = pid as i32;
+            self.slot = CHILD_GROUPS.iter().position(|slot| {
+                slot.compare_exchange(0, group, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+            });
+
+            let received = RECEIVED.load(Ordering::SeqCst);
+            if received != 0 {
+                // SAFETY: kill has no memory-safety requirements
+                unsafe {
+                    libc::kill(-group, received);
+                }
+                DELIVERED.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    impl Drop for Forwarding {
+        fn drop(&mut self) {
+            if let Some(slot) = self.slot {
+                CHILD_GROUPS[slot].store(0, Ordering::SeqCst);
+            }
+
+            let mut installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+            installed.0 -= 1;
+            if installed.0 > 0 {
+                return;
+            }
+            for (signal, old) in installed.1.drain(..) {
+                // SAFETY: `old` was filled in by sigaction for this signal
+                unsafe {
+                    libc::sigaction(signal, &old, std::ptr::null_mut());
+                }
+            }
+            // No child got the signal (e.g. spawn failed); it is chitin's
+            let received = RECEIVED.swap(0, Ordering::SeqCst);
+            if received != 0 && !DELIVERED.load(Ordering::SeqCst) {
+                // SAFETY: raising a signal has no memory-safety requirements
+                unsafe {
+                    libc::raise(received);
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn tracked_children() -> usize {
+        CHILD_GROUPS
+            .iter()
+            .filter(|slot| slot.load(Ordering::SeqCst) > 0)
+            .count()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Forwarding state is process-wide, so these tests run one at a time
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_signal_exit_code() {
+        let _guard = SERIAL.lock().unwrap();
+        let killed = output(Command::new("sh").args(["-c", "kill -TERM $$"])).unwrap();
+        assert_eq!(killed.status.signal(), Some(libc::SIGTERM));
+        assert_eq!(code(killed.status), 128 + libc::SIGTERM);
+
+        let failed = output(Command::new("sh").args(["-c", "echo hi; exit 3"])).unwrap();
+        assert_eq!(failed.stdout, b"hi\n");
+        assert_eq!(code(failed.status), 3);
+    }
+
+    #[test]
+    fn test_forwards_signals_to_child() {
+        let _guard = SERIAL.lock().unwrap();
+        let child = std::thread::spawn(|| output(Command::new("sleep").arg("10")).unwrap());
+
+        while unix::tracked_children() == 0 {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        // SAFETY: the forwarding handler is installed while a child is tracked
+        unsafe {
+            libc::kill(libc::getpid(), libc::SIGINT);
+        }
+
+        let interrupted = child.join().unwrap();
+        assert_eq!(interrupted.status.signal(), Some(libc::SIGINT));
+        assert_eq!(unix::tracked_children(), 0);
+    }
+
+    #[test]
+    fn test_forwards_signals_to_concurrent_children() {
+        let _guard = SERIAL.lock().unwrap();
+        let children: Vec<_> = (0..2)
+            .map(|_| std::thread::spawn(|| output(Command::new("sleep").arg("10")).unwrap()))
+            .collect();
+
+        while unix::tracked_children() < 2 {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        // SAFETY: the forwarding handler is installed while children are tracked
+        unsafe {
+            libc::kill(libc::getpid(), libc::SIGINT);
+        }
+
+        for child in children {
+            assert_eq!(child.join().unwrap().status.signal(), Some(libc::SIGINT));
+        }
+        assert_eq!(unix::tracked_children(), 0);
+
+        // The original disposition is back once the last capture finishes
+        // SAFETY: querying a disposition only fills in `current`
+        let current = unsafe {
+            let mut current: libc::sigaction = std::mem::zeroed();
+            libc::sigaction(libc::SIGINT, std::ptr::null(), &mut current);
+            current
+        };
+        assert_eq!(current.sa_sigaction, libc::SIG_DFL);
+    }
+
+    #[test]
+    fn test_forwards_signals_received_before_spawn() {
+        use std::os::unix::process::CommandExt;
+
+        let _guard = SERIAL.lock().unwrap();
+        let mut forwarding = unix::Forwarding::install();
+        // SAFETY: the forwarding handler is installed, so this only records it
+        unsafe {
+            libc::raise(libc::SIGTERM);
+        }
+
+        let child = Command::new("sleep")
+            .arg("10")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        forwarding.track(child.id());
+        let terminated = child.wait_with_output().unwrap();
+        drop(forwarding);
+        assert_eq!(terminated.status.signal(), Some(libc::SIGTERM));
+    }
+}
@@ -0,0 +1,537 @@
+//! Command tree module - parses openclaw's Commander-style help text into a typed
+//! model of commands, options, arguments and examples.
+
+use serde::{Deserialize, Serialize};
+
+/// A command parsed from help output
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandNode {
+    /// Command name (empty for the root command)
+    pub name: String,
+    /// Alternative names accepted for this command
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Usage line without the "Usage:" prefix
+    #[serde(default)]
+    pub usage: String,
+    /// Command description
+    #[serde(default)]
+    pub description: String,
+    /// Options accepted by this command
+    #[serde(default)]
+    pub options: Vec<OptionSpec>,
+    /// Positional arguments accepted by this command
+    #[serde(default)]
+    pub arguments: Vec<ArgumentSpec>,
+    /// Subcommands; entries only listed by the parent help have no options of their own
+    #[serde(default)]
+    pub subcommands: Vec<CommandNode>,
+    /// Usage examples
+    #[serde(default)]
+    pub examples: Vec<Example>,
+}
+
+/// An option such as `-p, --port <port>`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OptionSpec {
+    /// Short flag including the dash (e.g. "-p")
+    pub short: Option<String>,
+    /// Long flag including the dashes (e.g. "--port")
+    pub long: Option<String>,
+    /// Value name if the option takes a value (e.g. "port")
+    pub value: Option<String>,
+    /// Whether the value is required (`<value>`) rather than optional (`[value]`)
+    #[serde(default)]
+    pub value_required: bool,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A positional argument such as `<channel>` or `[targets...]`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentSpec {
+    pub name: String,
+    pub required: bool,
+    #[serde(default)]
+    pub variadic: bool,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// An entry from the "Examples:" section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Example {
+    pub command: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl CommandNode {
+    /// Find a direct subcommand by name or alias
+    pub fn find_subcommand(&self, name: &str) -> Option<&CommandNode> {
+        self.subcommands
+            .iter()
+            .find(|c| c.name == name || c.aliases.iter().any(|a| a == name))
+    }
+
+    /// Find an option by its short or long flag
+    pub fn find_option(&self, flag: &str) -> Option<&OptionSpec> {
+        self.options
+            .iter()
+            .find(|o| o.short.as_deref() == Some(flag) || o.long.as_deref() == Some(flag))
+    }
+
+    /// Resolve the subcommand path named by command-line arguments, skipping options
+    /// and their values. Aliases resolve to canonical names. A level whose own help
+    /// is unknown takes the next word as a subcommand unless its listing shows
+    /// positional arguments, and resolution stops there. Unknown options are
+    /// assumed to take a value unless the next word is a known subcommand.
+    pub fn command_path(&self, args: &[String]) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut node = Some(self);
+        let mut args = args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                break;
+            }
+
+            if arg.starts_with('-') {
+                let takes_value = !arg.contains('=')
+                    && match node.and_then(|n| n.find_option(arg)) {
+                        Some(option) => option.value.is_some(),
+                        None => arg.starts_with("--"),
+                    };
+                let known = node.is_some_and(|n| n.find_option(arg).is_some());
+                let value_follows = args.peek().is_some_and(|next| {
+                    !next.starts_with('-')
+                        && (known || node.is_none_or(|n| n.find_subcommand(next).is_none()))
+                });
+                if takes_value && value_follows {
+                    args.next();
+                }
+                continue;
+            }
+
+            let Some(current) = node else {
+                // Words after a guessed level may be subcommands or arguments
+                break;
+            };
+            if let Some(subcommand) = current.find_subcommand(arg) {
+                path.push(subcommand.name.clone());
+                node = Some(subcommand);
+            } else if current.usage.is_empty() && current.arguments.is_empty() {
+                // Help for this level was never parsed; assume a nested command
+                path.push(arg.clone());
+                node = None;
+            } else {
+                // A positional argument ends the command path
+                break;
+            }
+        }
+
+        path
+    }
+
+    /// Command path named by the usage line, e.g. `["channels", "login"]` for
+    /// "openclaw channels login [options] <channel>"
+    pub fn usage_path(&self) -> Vec<String> {
+        self.usage
+            .split_whitespace()
+            .skip(1)
+            .take_while(|w| !w.starts_with('[') && !w.starts_with('<'))
+            .map(|w| w.split('|').next().unwrap_or(w).to_string())
+            .collect()
+    }
+}
+
+impl OptionSpec {
+    /// All flags for this option (short first)
+    pub fn flags(&self) -> impl Iterator<Item = &str> {
+        self.short
+            .iter()
+            .chain(self.long.iter())
+            .map(|s| s.as_str())
+    }
+}
+
+/// Section of the help text currently being parsed
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Description,
+    Options,
+    Arguments,
+    Commands,
+    Examples,
+    Other,
+}
+
+/// Parse help text into a command node
+pub fn parse_help(text: &str) -> CommandNode {
+    let mut node = CommandNode::default();
+    let mut section = Section::Other;
+    let mut description: Vec<&str> = Vec::new();
+    let mut described_arguments: Vec<ArgumentSpec> = Vec::new();
+    // Section of the most recent entry, whose description continuation lines extend
+    let mut last_entry: Option<Section> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if !line.starts_with(' ') && !trimmed.is_empty() {
+            last_entry = None;
+            if let Some(usage) = trimmed.strip_prefix("Usage:") {
+                node.usage = usage.trim().to_string();
+                node.name = command_name(&node.usage);
+                section = Section::Description;
+                continue;
+            }
+            section = match trimmed {
+                "Options:" => Section::Options,
+                "Arguments:" => Section::Arguments,
+                "Commands:" => Section::Commands,
+                "Examples:" => Section::Examples,
+                _ if section == Section::Description => {
+                    description.push(trimmed);
+                    continue;
+                }
+                _ => Section::Other,
+            };
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+
+        // Deeper-indented lines continue the previous entry's description
+        if indent > 2 {
+            let desc = match last_entry {
+                Some(Section::Options) => node.options.last_mut().map(|o| &mut o.description),
+                Some(Section::Arguments) => {
+                    described_arguments.last_mut().map(|a| &mut a.description)
+                }
+                Some(Section::Commands) => node.subcommands.last_mut().map(|c| &mut c.description),
+                Some(Section::Examples) => node.examples.last_mut().map(|e| &mut e.description),
+                _ => None,
+            };
+            if let Some(desc) = desc {
+                if !desc.is_empty() {
+                    desc.push(' ');
+                }
+                desc.push_str(trimmed);
+            }
+            continue;
+        }
+
+        let (term, desc) = split_entry(trimmed);
+        last_entry = Some(section);
+        match section {
+            Section::Options => node.options.push(parse_option(term, desc)),
+            Section::Arguments => {
+                let mut argument = parse_argument(term);
+                argument.description = desc.to_string();
+                described_arguments.push(argument);
+            }
+            Section::Commands => {
+                let command = parse_command_entry(term, desc);
+                if !command.name.is_empty() {
+                    node.subcommands.push(command);
+                }
+            }
+            Section::Examples => node.examples.push(Example {
+                command: trimmed.to_string(),
+                description: String::new(),
+            }),
+            Section::Description => description.push(trimmed),
+            Section::Other => {}
+        }
+    }
+
+    node.description = description.join("\n");
+
+    // Arguments come from the usage line, with descriptions from the "Arguments:" section
+    node.arguments = usage_arguments(&node.usage);
+    for described in described_arguments {
+        match node.arguments.iter_mut().find(|a| a.name == described.name) {
+            Some(argument) => argument.description = described.description,
+            None => node.arguments.push(described),
+        }
+    }
+
+    node
+}
+
+/// Split an entry line into its term and description (separated by 2+ spaces)
+fn split_entry(line: &str) -> (&str, &str) {
+    match line.find("  ") {
+        Some(pos) => (&line[..pos], line[pos..].trim()),
+        None => (line, ""),
+    }
+}
+
+/// Extract the command name from a usage line ("openclaw channels login [options]")
+fn command_name(usage: &str) -> String {
+    let words: Vec<&str> = usage
+        .split_whitespace()
+        .take_while(|w| !w.starts_with('[') && !w.starts_with('<'))
+        .collect();
+
+    // The first word is the program name, which belongs to the root command
+    if words.len() > 1 {
+        words[words.len() - 1]
+            .split('|')
+            .next()
+            .unwrap_or("")
+            .to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Parse positional arguments from a usage line
+fn usage_arguments(usage: &str) -> Vec<ArgumentSpec> {
+    usage
+        .split_whitespace()
+        .filter(|w| w.starts_with('<') || (w.starts_with('[') && *w != "[options]"))
+        .filter(|w| *w != "[command]")
+        .map(parse_argument)
+        .collect()
+}
+
+/// Parse an argument term like `<name>`, `[name]` or `<names...>`
+fn parse_argument(term: &str) -> ArgumentSpec {
+    let term = term.trim();
+    let required = !term.starts_with('[');
+    let inner = term.trim_matches(|c| c == '<' || c == '>' || c == '[' || c == ']');
+    let variadic = inner.ends_with("...");
+
+    ArgumentSpec {
+        name: inner.trim_end_matches("...").to_string(),
+        required,
+        variadic,
+        description: String::new(),
+    }
+}
+
+/// Parse an option term like `-p, --port <port>`
+fn parse_option(term: &str, description: &str) -> OptionSpec {
+    let mut option = OptionSpec {
+        description: description.to_string(),
+        ..Default::default()
+    };
+
+    for part in term.split([',', ' ']).filter(|p| !p.is_empty()) {
+        if part.starts_with("--") {
+            let mut pieces = part.splitn(2, '=');
+            option.long = pieces.next().map(String::from);
+            if let Some(value) = pieces.next() {
+                option.value_required = !value.starts_with('[');
+                option.value = Some(parse_argument(value).name);
+            }
+        } else if part.starts_with('-') {
+            option.short = Some(part.to_string());
+        } else if part.starts_with('<') || part.starts_with('[') {
+            option.value_required = part.starts_with('<');
+            option.value = Some(parse_argument(part).name);
+        }
+    }
+
+    option
+}
+
+/// Parse a command listing entry like `login|l [options] <channel>`
+fn parse_command_entry(term: &str, description: &str) -> CommandNode {
+    let mut words = term.split_whitespace();
+    let mut names = words
+        .next()
+        .unwrap_or("")
+        .split('|')
+        .filter(|n| !n.is_empty())
+        .map(String::from);
+
+    CommandNode {
+        name: names.next().unwrap_or_default(),
+        aliases: names.collect(),
+        description: description.to_string(),
+        arguments: words
+            .filter(|w| *w != "[options]" && *w != "[command]")
+            .map(parse_argument)
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_HELP: &str = "🦞 OpenClaw 2026.5.28 — Your claws, your rules.
+
+Usage: openclaw [options] [command]
+
+Options:
+  -V, --version         output the version number
+  --profile <name>      Use a named profile
+  --log-level [level]   Set the log level
+  -h, --help            display help for command
+
+Commands:
+  channels|ch           Manage chat channels
+  gateway [options]     Run the gateway
+                        in the foreground
+  message <text...>     Send a message
+  help [command]        display help for command
+
+Examples:
+  openclaw channels login --verbose
+    Link personal WhatsApp Web and show QR.
+  openclaw gateway --port 18789
+
+Docs: https://docs.openclaw.ai/cli
+";
+
+    const LOGIN_HELP: &str = "Usage: openclaw channels login [options] <channel> [account]
+
+Link a channel account
+interactively
+
+Arguments:
+  channel          Channel to log in to
+
+Options:
+  --verbose        Verbose output
+  -h, --help       display help for command
+";
+
+    #[test]
+    fn test_parse_main_help() {
+        let node = parse_help(MAIN_HELP);
+        assert_eq!(node.name, "");
+        assert_eq!(node.usage, "openclaw [options] [command]");
+        assert!(node.arguments.is_empty());
+
+        assert_eq!(node.options.len(), 4);
+        let version = node.find_option("--version").unwrap();
+        assert_eq!(version.short.as_deref(), Some("-V"));
+        assert_eq!(version.value, None);
+        let profile = node.find_option("--profile").unwrap();
+        assert_eq!(profile.value.as_deref(), Some("name"));
+        assert!(profile.value_required);
+        let log_level = node.find_option("--log-level").unwrap();
+        assert_eq!(log_level.value.as_deref(), Some("level"));
+        assert!(!log_level.value_required);
+
+        assert_eq!(node.subcommands.len(), 4);
+        let channels = node.find_subcommand("ch").unwrap();
+        assert_eq!(channels.name, "channels");
+        assert_eq!(channels.aliases, vec!["ch"]);
+        let gateway = node.find_subcommand("gateway").unwrap();
+        assert_eq!(gateway.description, "Run the gateway in the foreground");
+        let message = node.find_subcommand("message").unwrap();
+        assert_eq!(message.arguments[0].name, "text");
+        assert!(message.arguments[0].variadic);
+
+        assert_eq!(node.examples.len(), 2);
+        assert_eq!(
+            node.examples[0].command,
+            "openclaw channels login --verbose"
+        );
+        assert_eq!(
+            node.examples[0].description,
+            "Link personal WhatsApp Web and show QR."
+        );
+        assert_eq!(node.examples[1].description, "");
+    }
+
+    #[test]
+    fn test_parse_subcommand_help() {
+        let node = parse_help(LOGIN_HELP);
+        assert_eq!(node.name, "login");
+        assert_eq!(node.usage_path(), vec!["channels", "login"]);
+        assert!(parse_help(MAIN_HELP).usage_path().is_empty());
+        assert_eq!(node.description, "Link a channel account\ninteractively");
+
+        assert_eq!(node.arguments.len(), 2);
+        assert_eq!(node.arguments[0].name, "channel");
+        assert!(node.arguments[0].required);
+        assert_eq!(node.arguments[0].description, "Channel to log in to");
+        assert_eq!(node.arguments[1].name, "account");
+        assert!(!node.arguments[1].required);
+
+        assert!(node.find_option("--verbose").is_some());
+        assert!(node.find_option("-h").is_some());
+        assert!(node.subcommands.is_empty());
+    }
+
+    #[test]
+    fn test_command_path() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut root = parse_help(MAIN_HELP);
+
+        // Option values are not part of the path; aliases resolve to names
+        assert_eq!(
+            root.command_path(&args(&["--profile", "work", "ch", "login", "--help"])),
+            vec!["channels", "login"]
+        );
+        assert_eq!(
+            root.command_path(&args(&["--profile=work", "gateway", "-h"])),
+            vec!["gateway"]
+        );
+        // Listed positional arguments end the path
+        assert_eq!(
+            root.command_path(&args(&["message", "hello", "--help"])),
+            vec!["message"]
+        );
+        // Unknown words at a parsed level are positionals
+        assert!(root.command_path(&args(&["nope", "--help"])).is_empty());
+
+        // Once a level's help is known, its options and positionals are respected
+        root.subcommands[0].subcommands = vec![parse_help(LOGIN_HELP)];
+        root.subcommands[0].usage = "openclaw channels [options] [command]".to_string();
+        assert_eq!(
+            root.command_path(&args(&["channels", "login", "whatsapp", "--verbose", "-h"])),
+            vec!["channels", "login"]
+        );
+        assert_eq!(
+            root.command_path(&args(&["channels", "other", "-h"])),
+            vec!["channels"]
+        );
+
+        // Without any cached help only one word is guessed to be a command
+        let empty = CommandNode::default();
+        assert_eq!(
+            empty.command_path(&args(&["channels", "login", "--help"])),
+            vec!["channels"]
+        );
+
+        // Unknown options are assumed to take a value
+        assert_eq!(
+            empty.command_path(&args(&["--profile", "work", "channels", "--help"])),
+            vec!["channels"]
+        );
+        let message = CommandNode {
+            name: "message".to_string(),
+            usage: "openclaw message [options] [command]".to_string(),
+            subcommands: vec![CommandNode {
+                name: "send".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let tree = CommandNode {
+            usage: "openclaw [options] [command]".to_string(),
+            subcommands: vec![message],
+            ..Default::default()
+        };
+        let send = args(&["message", "send", "--to", "+1555", "--help"]);
+        assert_eq!(tree.command_path(&send), vec!["message", "send"]);
+        // ...unless the next word is a known subcommand
+        assert_eq!(
+            tree.command_path(&args(&["--verbose", "message", "--help"])),
+            vec!["message"]
+        );
+    }
+}
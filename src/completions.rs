@@ -0,0 +1,261 @@
+//! Shell completion module - emits completion scripts and answers completion
+//! queries from the help cache without spawning Node.js.
+
+use anyhow::Result;
+
+use crate::cache::HelpCache;
+
+/// Commands implemented by chitin itself, offered alongside openclaw's commands
+const NATIVE_COMMANDS: &[(&str, &str)] = &[
+    ("cache", "Inspect, clear or rebuild the help cache"),
+    ("completions", "Generate shell completion scripts"),
+    ("daemon", "Manage the warm Node.js worker"),
+    ("shim", "Install chitin as `openclaw`"),
+];
+
+const BASH_SCRIPT: &str = r#"# bash completion for chitin
+_chitin() {
+    local IFS=$'\n'
+    COMPREPLY=($(chitin __complete "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null | cut -f1))
+}
+complete -o default -F _chitin chitin
+"#;
+
+const ZSH_SCRIPT: &str = r#"#compdef chitin
+_chitin() {
+    local -a candidates
+    local line name desc
+    for line in "${(@f)$(chitin __complete "${(@)words[2,CURRENT]}" 2>/dev/null)}"; do
+        [[ -n $line ]] || continue
+        name=${line%%$'\t'*}
+        desc=${line#*$'\t'}
+        [[ $desc == $line ]] && desc=
+        candidates+=("${name//:/\\:}:$desc")
+    done
+    if (( ${#candidates} )); then
+        _describe 'chitin' candidates
+    else
+        _files
+    fi
+}
+compdef _chitin chitin
+"#;
+
+const FISH_SCRIPT: &str = r#"# fish completion for chitin
+function __chitin_complete
+    set -l tokens (commandline -opc)
+    set -l current (commandline -ct)
+    chitin __complete $tokens[2..-1] "$current" 2>/dev/null
+end
+complete -c chitin -f -a '(__chitin_complete)'
+"#;
+
+/// Shells we can generate completion scripts for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    /// Parse a shell name as given on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            _ => None,
+        }
+    }
+
+    /// Completion script for this shell
+    pub fn script(&self) -> &'static str {
+        match self {
+            Shell::Bash => BASH_SCRIPT,
+            Shell::Zsh => ZSH_SCRIPT,
+            Shell::Fish => FISH_SCRIPT,
+        }
+    }
+}
+
+/// A single completion candidate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub value: String,
+    pub description: String,
+}
+
+/// Print the completion script for the shell named in `args`
+pub fn print_script(args: &[String]) -> Result<()> {
+    let shell = match args.first().map(|s| s.as_str()) {
+        Some(name) => Shell::from_name(name),
+        None => None,
+    };
+
+    match shell {
+        Some(shell) => {
+            print!("{}", shell.script());
+            Ok(())
+        }
+        None => anyhow::bail!("Usage: chitin completions <bash|zsh|fish>"),
+    }
+}
+
+/// Answer a completion query and print one candidate per line
+/// (value and description separated by a tab)
+pub fn run(words: &[String]) -> Result<()> {
+    let cache = HelpCache::new()?;
+
+    for candidate in complete(&cache, words)? {
+        if candidate.description.is_empty() {
+            println!("{}", candidate.value);
+        } else {
+            println!("{}\t{}", candidate.value, candidate.description);
+        }
+    }
+    Ok(())
+}
+
+/// Compute completion candidates for the words typed after `chitin`.
+/// The last word is the one being completed (possibly empty).
+pub fn complete(cache: &HelpCache, words: &[String]) -> Result<Vec<Candidate>> {
+    let (current, previous) = match words.split_last() {
+        Some((current, previous)) => (current.as_str(), previous),
+        None => ("", words),
+    };
+
+    let root = cache
+        .get_command_tree(crate::openclaw::version(), crate::CHITIN_VERSION)?
+        .unwrap_or_default();
+    let mut node = &root;
+    let mut expects_value = false;
+
+    for word in previous {
+        if expects_value {
+            expects_value = false;
+            continue;
+        }
+
+        if word.starts_with('-') {
+            expects_value =
+                !word.contains('=') && node.find_option(word).is_some_and(|o| o.value_required);
+            continue;
+        }
+
+        if let Some(subcommand) = node.find_subcommand(word) {
+            node = subcommand;
+        }
+    }
+
+    // The shell falls back to file completion for option values
+    if expects_value {
+        return Ok(Vec::new());
+    }
+
+    let mut candidates = Vec::new();
+    if current.starts_with('-') {
+        for option in &node.options {
+            for flag in option.flags().filter(|f| f.starts_with(current)) {
+                candidates.push(Candidate {
+                    value: flag.to_string(),
+                    description: option.description.clone(),
+                });
+            }
+        }
+    } else {
+        for subcommand in &node.subcommands {
+            let names = std::iter::once(&subcommand.name).chain(subcommand.aliases.iter());
+            for name in names.filter(|n| n.starts_with(current)) {
+                candidates.push(Candidate {
+                    value: name.clone(),
+                    description: subcommand.description.clone(),
+                });
+            }
+        }
+
+        if std::ptr::eq(node, &root) {
+            for (name, description) in NATIVE_COMMANDS {
+                if name.starts_with(current) && !candidates.iter().any(|c| c.value == *name) {
+                    candidates.push(Candidate {
+                        value: name.to_string(),
+                        description: description.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_HELP: &str = "chitin 0.1.0 (openclaw 2026.5.28)
+
+Usage: chitin [options] [command]
+
+Options:
+  -V, --version       output the version number
+  --profile <name>    Use a named profile
+  -h, --help          display help for command
+
+Commands:
+  channels|ch         Manage chat channels
+  gateway [options]   Run the gateway
+                      in the foreground
+  help [command]      display help for command
+";
+
+    #[test]
+    fn test_complete_from_cache() {
+        let cache = HelpCache::with_path(
+            std::env::temp_dir().join(format!("chitin-complete-{}.json", std::process::id())),
+        );
+        cache.clear().unwrap();
+        cache
+            .save_help(MAIN_HELP, crate::openclaw::version(), crate::CHITIN_VERSION)
+            .unwrap();
+
+        let words = |w: &[&str]| w.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let values = |c: Vec<Candidate>| c.into_iter().map(|c| c.value).collect::<Vec<_>>();
+
+        assert_eq!(
+            values(complete(&cache, &words(&["g"])).unwrap()),
+            vec!["gateway"]
+        );
+        assert_eq!(
+            values(complete(&cache, &words(&["c"])).unwrap()),
+            vec!["channels", "ch", "cache", "completions"]
+        );
+        assert_eq!(
+            values(complete(&cache, &words(&["--p"])).unwrap()),
+            vec!["--profile"]
+        );
+        // Option values are left to the shell
+        assert!(
+            complete(&cache, &words(&["--profile", ""]))
+                .unwrap()
+                .is_empty()
+        );
+        // Uncached subcommand yields nothing
+        assert!(
+            complete(&cache, &words(&["gateway", "--"]))
+                .unwrap()
+                .is_empty()
+        );
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_shell_scripts() {
+        assert_eq!(Shell::from_name("bash"), Some(Shell::Bash));
+        assert_eq!(Shell::from_name("powershell"), None);
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            assert!(shell.script().contains("chitin __complete"));
+        }
+    }
+}
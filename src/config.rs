@@ -0,0 +1,355 @@
+//! Configuration module - loads chitin settings from `config.toml`, with every
+//! key overridable through a `CHITIN_*` environment variable.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::installer::PackageManager;
+use crate::runtime::JsRuntime;
+
+/// Default age after which cached help is refreshed in the background (24 hours)
+pub const DEFAULT_CACHE_TTL: u64 = 24 * 60 * 60;
+
+/// Effective chitin configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Directory holding the help cache (default `~/.chitin/cache`)
+    pub cache_dir: Option<PathBuf>,
+    /// Age in seconds after which cached help is refreshed
+    pub cache_ttl: u64,
+    /// openclaw.mjs files or package directories searched before the built-in locations
+    pub openclaw_paths: Vec<PathBuf>,
+    /// Package manager to prefer when both are available
+    pub package_manager: Option<PackageManager>,
+    /// JavaScript runtime for openclaw (default: Node.js, Bun when Node.js is missing)
+    pub runtime: Option<JsRuntime>,
+    /// Replace "openclaw" with "chitin" in help output
+    pub rebrand: bool,
+    /// Run delegated commands through the warm daemon
+    pub daemon: bool,
+    /// Run commands chitin implements natively instead of delegating them
+    pub native: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cache_dir: None,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            openclaw_paths: Vec::new(),
+            package_manager: None,
+            runtime: None,
+            rebrand: true,
+            daemon: false,
+            native: true,
+        }
+    }
+}
+
+/// Durations may be given in seconds or as a string such as "30m", "12h" or "7d"
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DurationValue {
+    Seconds(u64),
+    Text(String),
+}
+
+/// Keys accepted in config.toml
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    cache_dir: Option<PathBuf>,
+    cache_ttl: Option<DurationValue>,
+    openclaw_paths: Option<Vec<PathBuf>>,
+    package_manager: Option<PackageManager>,
+    runtime: Option<JsRuntime>,
+    rebrand: Option<bool>,
+    daemon: Option<bool>,
+    native: Option<bool>,
+}
+
+impl Config {
+    /// Parse config.toml contents on top of the defaults
+    fn from_toml(content: &str) -> Result<Self> {
+        let file: FileConfig = toml::from_str(content)?;
+        let mut config = Config::default();
+
+        if let Some(dir) = file.cache_dir {
+            config.cache_dir = Some(expand_home(&dir));
+        }
+        if let Some(ttl) = file.cache_ttl {
+            config.cache_ttl = match ttl {
+                DurationValue::Seconds(secs) => secs,
+                DurationValue::Text(text) => parse_duration(&text).context("invalid cache_ttl")?,
+            };
+        }
+        if let Some(paths) = file.openclaw_paths {
+            config.openclaw_paths = paths.iter().map(|p| expand_home(p)).collect();
+        }
+        config.package_manager = file.package_manager;
+        config.runtime = file.runtime;
+        config.rebrand = file.rebrand.unwrap_or(config.rebrand);
+        config.daemon = file.daemon.unwrap_or(config.daemon);
+        config.native = file.native.unwrap_or(config.native);
+
+        Ok(config)
+    }
+
+    /// Apply `CHITIN_*` overrides, reading variables through `var`. Invalid
+    /// variables are skipped and returned, so the others still apply.
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Vec<anyhow::Error> {
+        let mut errors = Vec::new();
+
+        if let Some(dir) = var("CHITIN_CACHE_DIR") {
+            self.cache_dir = Some(expand_home(Path::new(&dir)));
+        }
+        if let Some(ttl) = env_value(&var, "CHITIN_CACHE_TTL", parse_duration, &mut errors) {
+            self.cache_ttl = ttl;
+        }
+        if let Some(paths) = var("CHITIN_OPENCLAW_PATHS") {
+            self.openclaw_paths = std::env::split_paths(&paths)
+                .filter(|p| !p.as_os_str().is_empty())
+                .map(|p| expand_home(&p))
+                .collect();
+        }
+        if let Some(pm) = env_value(
+            &var,
+            "CHITIN_PACKAGE_MANAGER",
+            PackageManager::from_name,
+            &mut errors,
+        ) {
+            self.package_manager = Some(pm);
+        }
+        if let Some(runtime) = env_value(&var, "CHITIN_RUNTIME", JsRuntime::from_name, &mut errors)
+        {
+            self.runtime = Some(runtime);
+        }
+        if let Some(rebrand) = env_value(&var, "CHITIN_REBRAND", parse_bool, &mut errors) {
+            self.rebrand = rebrand;
+        }
+        if let Some(daemon) = env_value(&var, "CHITIN_DAEMON", parse_bool, &mut errors) {
+            self.daemon = daemon;
+        }
+        if let Some(native) = env_value(&var, "CHITIN_NATIVE", parse_bool, &mut errors) {
+            self.native = native;
+        }
+        errors
+    }
+}
+
+/// Parse variable `name` if it is set, collecting the error if it is invalid
+fn env_value<T>(
+    var: impl Fn(&str) -> Option<String>,
+    name: &str,
+    parse: impl FnOnce(&str) -> Result<T>,
+    errors: &mut Vec<anyhow::Error>,
+) -> Option<T> {
+    match parse(&var(name)?) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            errors.push(e.context(format!("invalid {}", name)));
+            None
+        }
+    }
+}
+
+/// Get the effective configuration, loaded once per process. An invalid
+/// config file or variable is reported rather than aborting the command.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        let mut config = match config_path(var) {
+            Some(path) => match load_file(&path) {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("chitin: ignoring {}: {:#}", path.display(), e);
+                    Config::default()
+                }
+            },
+            None => Config::default(),
+        };
+
+        for e in config.apply_env(var) {
+            eprintln!("chitin: {:#}", e);
+        }
+        config
+    })
+}
+
+fn load_file(path: &Path) -> Result<Config> {
+    let content = fs::read_to_string(path).context("Failed to read config file")?;
+    Config::from_toml(&content)
+}
+
+/// Locate the config file: `$CHITIN_CONFIG`, then `~/.chitin/config.toml`, then
+/// `$XDG_CONFIG_HOME/chitin/config.toml` (default `~/.config`)
+fn config_path(var: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    if let Some(path) = var("CHITIN_CONFIG") {
+        return Some(expand_home(Path::new(&path)));
+    }
+
+    let home = dirs::home_dir();
+    let xdg_config = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|h| h.join(".config")));
+
+    let candidates = [
+        home.map(|h| h.join(".chitin").join("config.toml")),
+        xdg_config.map(|d| d.join("chitin").join("config.toml")),
+    ];
+    candidates.into_iter().flatten().find(|p| p.exists())
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Parse a duration in seconds, optionally suffixed with s, m, h or d
+fn parse_duration(text: &str) -> Result<u64> {
+    let text = text.trim();
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => text.split_at(i),
+        None => (text, "s"),
+    };
+    let multiplier = match unit.trim() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => anyhow::bail!("'{}' is not a duration (e.g. 3600, 30m, 12h, 7d)", text),
+    };
+    let number: u64 = number
+        .parse()
+        .with_context(|| format!("'{}' is not a duration (e.g. 3600, 30m, 12h, 7d)", text))?;
+    number
+        .checked_mul(multiplier)
+        .with_context(|| format!("'{}' is too long a duration", text))
+}
+
+fn parse_bool(text: &str) -> Result<bool> {
+    match text.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => anyhow::bail!("'{}' is not a boolean", text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::from_toml("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.cache_ttl, DEFAULT_CACHE_TTL);
+        assert!(config.rebrand);
+        assert!(!config.daemon);
+        assert!(config.native);
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let config = Config::from_toml(
+            r#"
+cache_dir = "/var/cache/chitin"
+cache_ttl = "12h"
+openclaw_paths = ["/opt/openclaw/openclaw.mjs"]
+package_manager = "npm"
+runtime = "bun"
+rebrand = false
+daemon = true
+native = false
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.cache_dir, Some(PathBuf::from("/var/cache/chitin")));
+        assert_eq!(config.cache_ttl, 12 * 60 * 60);
+        assert_eq!(
+            config.openclaw_paths,
+            vec![PathBuf::from("/opt/openclaw/openclaw.mjs")]
+        );
+        assert_eq!(config.package_manager, Some(PackageManager::Npm));
+        assert_eq!(config.runtime, Some(JsRuntime::Bun));
+        assert!(!config.rebrand);
+        assert!(config.daemon);
+        assert!(!config.native);
+
+        assert_eq!(Config::from_toml("cache_ttl = 600").unwrap().cache_ttl, 600);
+        assert!(Config::from_toml("cache_tll = 600").is_err());
+        assert!(Config::from_toml("package_manager = \"yarn\"").is_err());
+        assert!(Config::from_toml("runtime = \"deno\"").is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut config = Config::from_toml("cache_ttl = 600\nrebrand = false").unwrap();
+        let env: HashMap<&str, &str> = [
+            ("CHITIN_CACHE_TTL", "30m"),
+            ("CHITIN_REBRAND", "1"),
+            ("CHITIN_PACKAGE_MANAGER", "pnpm"),
+            ("CHITIN_OPENCLAW_PATHS", "/a/openclaw.mjs:/b"),
+            ("CHITIN_NATIVE", "0"),
+            ("CHITIN_RUNTIME", "Node"),
+        ]
+        .into_iter()
+        .collect();
+        assert!(
+            config
+                .apply_env(|name| env.get(name).map(|v| v.to_string()))
+                .is_empty()
+        );
+
+        assert_eq!(config.cache_ttl, 30 * 60);
+        assert!(config.rebrand);
+        assert_eq!(config.package_manager, Some(PackageManager::Pnpm));
+        assert_eq!(
+            config.openclaw_paths,
+            vec![PathBuf::from("/a/openclaw.mjs"), PathBuf::from("/b")]
+        );
+        assert!(!config.native);
+        assert_eq!(config.runtime, Some(JsRuntime::Node));
+
+        // Invalid variables are all reported, and the valid ones still apply
+        let env: HashMap<&str, &str> = [
+            ("CHITIN_DAEMON", "maybe"),
+            ("CHITIN_CACHE_TTL", "99999999999999999d"),
+            ("CHITIN_NATIVE", "1"),
+        ]
+        .into_iter()
+        .collect();
+        let errors = config.apply_env(|name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(errors.len(), 2);
+        assert!(config.native);
+        assert_eq!(config.cache_ttl, 30 * 60);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90").unwrap(), 90);
+        assert_eq!(parse_duration("45s").unwrap(), 45);
+        assert_eq!(parse_duration("2h").unwrap(), 7200);
+        assert_eq!(parse_duration("1d").unwrap(), 86400);
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("5w").is_err());
+        assert!(parse_duration("99999999999999999d").is_err());
+    }
+
+    #[test]
+    fn test_config_path_override() {
+        let path = config_path(|name| (name == "CHITIN_CONFIG").then(|| "/etc/chitin.toml".into()));
+        assert_eq!(path, Some(PathBuf::from("/etc/chitin.toml")));
+    }
+}
@@ -0,0 +1,342 @@
+//! Daemon module - runs delegated commands through a long-lived Node.js worker
+//! that keeps openclaw pre-warmed, talking to it over a Unix socket.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+/// Node.js side of the daemon, written to the daemon directory on start
+const WORKER_SCRIPT: &str = include_str!("daemon_worker.mjs");
+
+// Frame types, matching daemon_worker.mjs
+const REQUEST: u8 = 1;
+const STDIN: u8 = 2;
+const STDIN_EOF: u8 = 3;
+const STDOUT: u8 = 4;
+const STDERR: u8 = 5;
+const EXIT: u8 = 6;
+const STATUS: u8 = 8;
+const SHUTDOWN: u8 = 9;
+const INFO: u8 = 10;
+
+/// Check if daemon mode is enabled (`daemon = true` or CHITIN_DAEMON=1)
+pub fn enabled() -> bool {
+    crate::config::get().daemon
+}
+
+/// Get the daemon directory (~/.chitin/daemon)
+fn daemon_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Cannot find home directory")?;
+    Ok(home.join(".chitin").join("daemon"))
+}
+
+/// Socket for the daemon serving a given openclaw version. Upgrading openclaw
+/// changes the path, so a stale daemon is never used for a newer install.
+fn socket_path(dir: &Path, openclaw_version: &str) -> PathBuf {
+    dir.join(format!("openclaw-{}.sock", openclaw_version))
+}
+
+/// Write a single frame: [type: u8][length: u32 BE][payload]
+fn write_frame(writer: &mut impl Write, frame_type: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(5 + payload.len());
+    frame.push(frame_type);
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame)
+}
+
+/// Read a single frame, returning None at end of stream
+fn read_frame(reader: &mut impl Read) -> io::Result<Option<(u8, Vec<u8>)>> {
+    let mut header = [0u8; 5];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let length = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+    let mut payload = vec![0u8; length];
+    reader.read_exact(&mut payload)?;
+    Ok(Some((header[0], payload)))
+}
+
+/// Run a delegated command through the daemon for the installed openclaw version.
+/// Returns openclaw's exit status, or None if daemon mode is disabled, no daemon
+/// is running (one is started in the background for next time), or the request
+/// can't be sent as JSON (a non-UTF-8 environment, or a deleted working directory).
+pub fn run(args: &[String]) -> Result<Option<ExitStatus>> {
+    // A daemon left running doesn't override disabling daemon mode
+    if !enabled() {
+        return Ok(None);
+    }
+    let Some(mut env) = std::env::vars_os()
+        .map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+        .collect::<Option<HashMap<String, String>>>()
+    else {
+        return Ok(None);
+    };
+    let Some(cwd) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| cwd.into_os_string().into_string().ok())
+    else {
+        return Ok(None);
+    };
+    let dir = daemon_dir()?;
+    let version = crate::openclaw::version();
+
+    let mut stream = match UnixStream::connect(socket_path(&dir, version)) {
+        Ok(stream) => stream,
+        Err(_) => {
+            let _ = start(&dir, version);
+            return Ok(None);
+        }
+    };
+
+    // The worker's output is a pipe; keep colors if our own output is a terminal
+    if atty::is(atty::Stream::Stdout) && !env.contains_key("NO_COLOR") {
+        env.entry("FORCE_COLOR".to_string())
+            .or_insert_with(|| "1".to_string());
+    }
+    let request = serde_json::json!({
+        "argv": args,
+        "env": env,
+        "cwd": cwd,
+    });
+    write_frame(&mut stream, REQUEST, request.to_string().as_bytes())
+        .context("Failed to send request to chitin daemon")?;
+
+    let mut input = stream.try_clone()?;
+    std::thread::spawn(move || forward_stdin(&mut input));
+
+    loop {
+        match read_frame(&mut stream)? {
+            Some((STDOUT, data)) => {
+                let mut stdout = io::stdout().lock();
+                stdout.write_all(&data)?;
+                stdout.flush()?;
+            }
+            Some((STDERR, data)) => {
+                let mut stderr = io::stderr().lock();
+                stderr.write_all(&data)?;
+                stderr.flush()?;
+            }
+            Some((EXIT, data)) => {
+                let code: [u8; 4] = data
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("Invalid exit status from chitin daemon"))?;
+                return Ok(Some(exit_status(i32::from_be_bytes(code))));
+            }
+            Some(_) => {}
+            None => anyhow::bail!("chitin daemon closed the connection unexpectedly"),
+        }
+    }
+}
+
+/// Exit status for the worker's EXIT frame: openclaw's exit code, or minus the
+/// signal that killed it
+fn exit_status(value: i32) -> ExitStatus {
+    match value {
+        ..0 => ExitStatus::from_raw(-value),
+        code => ExitStatus::from_raw((code & 0xff) << 8),
+    }
+}
+
+/// Copy our stdin to the daemon until end of input
+fn forward_stdin(stream: &mut UnixStream) {
+    let mut stdin = io::stdin().lock();
+    let mut buf = [0u8; 8192];
+    loop {
+        match stdin.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                if write_frame(stream, STDIN, &buf[..n]).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+    let _ = write_frame(stream, STDIN_EOF, &[]);
+}
+
+/// Start a detached daemon for the given openclaw version, stopping daemons
+/// left over from other versions
+fn start(dir: &Path, openclaw_version: &str) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create daemon directory")?;
+    stop_all(dir, Some(openclaw_version));
+
+    let worker = dir.join(format!("worker-{}.mjs", crate::CHITIN_VERSION));
+    if fs::read_to_string(&worker).ok().as_deref() != Some(WORKER_SCRIPT) {
+        fs::write(&worker, WORKER_SCRIPT).context("Failed to write daemon worker")?;
+    }
+
+    let entry = crate::openclaw::find_entry()?;
+    let log = fs::File::create(dir.join("daemon.log")).context("Failed to create daemon log")?;
+
+    crate::runtime::node_command()
+        .arg(&worker)
+        .arg("server")
+        .arg(socket_path(dir, openclaw_version))
+        .arg(&entry)
+        .arg(openclaw_version)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(log)
+        // Own process group, so terminal signals aimed at chitin don't reach it
+        .process_group(0)
+        .spawn()
+        .context("Failed to start chitin daemon")?;
+
+    Ok(())
+}
+
+/// Send a control frame to a daemon and return its JSON reply
+fn control(socket: &Path, frame_type: u8) -> Result<serde_json::Value> {
+    let mut stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    write_frame(&mut stream, frame_type, &[])?;
+
+    match read_frame(&mut stream)? {
+        Some((INFO, data)) => Ok(serde_json::from_slice(&data)?),
+        _ => anyhow::bail!("Unexpected reply from chitin daemon"),
+    }
+}
+
+/// Stop all daemons except the one serving `keep_version`. Returns their pids.
+fn stop_all(dir: &Path, keep_version: Option<&str>) -> Vec<u64> {
+    let keep = keep_version.map(|v| socket_path(dir, v));
+    let mut stopped = Vec::new();
+
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let is_socket = path.extension().is_some_and(|e| e == "sock");
+        if !is_socket || Some(&path) == keep.as_ref() {
+            continue;
+        }
+
+        match control(&path, SHUTDOWN) {
+            Ok(info) => stopped.extend(info["pid"].as_u64()),
+            // Nobody listening: left behind by a daemon that was killed
+            Err(_) => {
+                let _ = fs::remove_file(&path);
+            }
+        }
+    }
+
+    stopped
+}
+
+/// Handle `chitin daemon <start|stop|status>`
+pub fn command(args: &[String]) -> Result<()> {
+    let dir = daemon_dir()?;
+    let version = crate::openclaw::version();
+    let socket = socket_path(&dir, version);
+
+    match args.first().map(|s| s.as_str()) {
+        Some("start") => {
+            if let Ok(info) = control(&socket, STATUS) {
+                println!("chitin daemon already running (pid {})", info["pid"]);
+                return Ok(());
+            }
+
+            start(&dir, version)?;
+
+            // Wait for the worker to start listening
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while Instant::now() < deadline {
+                if let Ok(info) = control(&socket, STATUS) {
+                    println!(
+                        "chitin daemon started (pid {}, openclaw {})",
+                        info["pid"], version
+                    );
+                    return Ok(());
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            anyhow::bail!(
+                "chitin daemon did not start; see {}",
+                dir.join("daemon.log").display()
+            )
+        }
+        Some("stop") => {
+            let stopped = stop_all(&dir, None);
+            if stopped.is_empty() {
+                println!("chitin daemon is not running");
+            }
+            for pid in stopped {
+                println!("Stopped chitin daemon (pid {})", pid);
+            }
+            Ok(())
+        }
+        Some("status") => match control(&socket, STATUS) {
+            Ok(info) => {
+                println!("chitin daemon running");
+                println!("  pid:      {}", info["pid"]);
+                println!(
+                    "  openclaw: {}",
+                    info["openclaw_version"].as_str().unwrap_or("")
+                );
+                println!(
+                    "  node:     {}",
+                    info["node_version"].as_str().unwrap_or("")
+                );
+                println!("  uptime:   {}s", info["uptime_secs"]);
+                println!("  requests: {}", info["requests"]);
+                println!("  socket:   {}", socket.display());
+                Ok(())
+            }
+            Err(_) => {
+                println!("chitin daemon is not running");
+                std::process::exit(1);
+            }
+        },
+        _ => anyhow::bail!("Usage: chitin daemon <start|stop|status>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, STDOUT, b"hello").unwrap();
+        write_frame(&mut buf, EXIT, &3i32.to_be_bytes()).unwrap();
+        assert_eq!(&buf[..5], &[STDOUT, 0, 0, 0, 5]);
+
+        let mut reader = io::Cursor::new(buf);
+        assert_eq!(
+            read_frame(&mut reader).unwrap(),
+            Some((STDOUT, b"hello".to_vec()))
+        );
+        assert_eq!(
+            read_frame(&mut reader).unwrap(),
+            Some((EXIT, vec![0, 0, 0, 3]))
+        );
+        assert_eq!(read_frame(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_exit_status() {
+        assert_eq!(exit_status(0).code(), Some(0));
+        assert_eq!(exit_status(3).code(), Some(3));
+        assert_eq!(exit_status(-libc::SIGINT).signal(), Some(libc::SIGINT));
+    }
+
+    #[test]
+    fn test_socket_path_is_per_version() {
+        let dir = Path::new("/tmp/chitin");
+        assert_ne!(socket_path(dir, "2026.5.28"), socket_path(dir, "2026.6.1"));
+        assert_eq!(
+            socket_path(dir, "2026.5.28"),
+            PathBuf::from("/tmp/chitin/openclaw-2026.5.28.sock")
+        );
+    }
+}
@@ -0,0 +1,196 @@
+// chitin daemon worker - hosts the openclaw CLI in pre-warmed Node.js processes.
+//
+//   node daemon_worker.mjs server <socket> <openclaw.mjs> <version>
+//   node daemon_worker.mjs runner <openclaw.mjs>
+//
+// The server listens on a Unix socket and keeps one warm runner ready. Each
+// request is handed to the warm runner (which then runs openclaw exactly once)
+// and a replacement runner is started immediately. Frames on the socket are
+// [type: u8][length: u32 BE][payload], matching src/daemon.rs.
+
+import fs from "node:fs";
+import net from "node:net";
+import os from "node:os";
+import path from "node:path";
+import module, { createRequire } from "node:module";
+import { fork } from "node:child_process";
+import { pathToFileURL } from "node:url";
+
+const REQUEST = 1;
+const STDIN = 2;
+const STDIN_EOF = 3;
+const STDOUT = 4;
+const STDERR = 5;
+const EXIT = 6;
+const STATUS = 8;
+const SHUTDOWN = 9;
+const INFO = 10;
+
+const IDLE_TIMEOUT_MS = 30 * 60 * 1000;
+
+const [mode, ...args] = process.argv.slice(2);
+if (mode === "runner") {
+  await runner(args[0]);
+} else if (mode === "server") {
+  server(args[0], args[1], args[2]);
+} else {
+  console.error("usage: daemon_worker.mjs server|runner ...");
+  process.exit(2);
+}
+
+function frame(type, payload) {
+  const data = Buffer.isBuffer(payload) ? payload : Buffer.from(payload ?? "");
+  const header = Buffer.alloc(5);
+  header[0] = type;
+  header.writeUInt32BE(data.length, 1);
+  return Buffer.concat([header, data]);
+}
+
+function readPackageVersion(entry) {
+  try {
+    const pkg = JSON.parse(fs.readFileSync(path.join(path.dirname(entry), "package.json"), "utf8"));
+    return pkg.version ?? "";
+  } catch {
+    return "";
+  }
+}
+
+function server(socketPath, entry, version) {
+  const started = Date.now();
+  let requests = 0;
+  let idleTimer = null;
+  let warm = spawnRunner();
+
+  function spawnRunner() {
+    const child = fork(process.argv[1], ["runner", entry], {
+      stdio: ["pipe", "pipe", "pipe", "ipc"],
+    });
+    child.on("error", () => {});
+    return child;
+  }
+
+  function resetIdleTimer() {
+    clearTimeout(idleTimer);
+    idleTimer = setTimeout(shutdown, IDLE_TIMEOUT_MS);
+    idleTimer.unref();
+  }
+
+  function shutdown() {
+    warm.kill("SIGTERM");
+    srv.close();
+    process.exit(0);
+  }
+
+  const srv = net.createServer((socket) => {
+    let buffer = Buffer.alloc(0);
+    let child = null;
+
+    socket.on("error", () => {});
+    socket.on("close", () => {
+      if (child && child.exitCode === null && child.signalCode === null) {
+        child.kill("SIGTERM");
+      }
+    });
+
+    socket.on("data", (chunk) => {
+      buffer = Buffer.concat([buffer, chunk]);
+      while (buffer.length >= 5) {
+        const length = buffer.readUInt32BE(1);
+        if (buffer.length < 5 + length) break;
+        handle(buffer[0], buffer.subarray(5, 5 + length));
+        buffer = buffer.subarray(5 + length);
+      }
+    });
+
+    function handle(type, payload) {
+      switch (type) {
+        case REQUEST: {
+          const request = JSON.parse(payload.toString());
+          requests += 1;
+          resetIdleTimer();
+
+          child = warm;
+          warm = spawnRunner();
+
+          child.stdout.on("data", (data) => socket.write(frame(STDOUT, data)));
+          child.stderr.on("data", (data) => socket.write(frame(STDERR, data)));
+          child.stdin.on("error", () => {});
+          child.on("close", (code, signal) => {
+            // A negative status is the signal that killed openclaw
+            const status = Buffer.alloc(4);
+            status.writeInt32BE(code ?? -(os.constants.signals[signal] ?? -1));
+            socket.end(frame(EXIT, status));
+          });
+          child.send(request);
+          break;
+        }
+        case STDIN:
+          child?.stdin.write(payload);
+          break;
+        case STDIN_EOF:
+          child?.stdin.end();
+          break;
+        case STATUS:
+          socket.end(
+            frame(
+              INFO,
+              JSON.stringify({
+                pid: process.pid,
+                openclaw_version: version,
+                node_version: process.version,
+                uptime_secs: Math.floor((Date.now() - started) / 1000),
+                requests,
+              }),
+            ),
+          );
+          break;
+        case SHUTDOWN:
+          socket.end(frame(INFO, JSON.stringify({ pid: process.pid })), shutdown);
+          break;
+      }
+    }
+  });
+
+  // Exit as soon as openclaw is upgraded; the next chitin call starts a fresh daemon
+  fs.watchFile(path.join(path.dirname(entry), "package.json"), { interval: 5000 }, () => {
+    if (readPackageVersion(entry) !== version) shutdown();
+  }).unref();
+
+  process.on("SIGTERM", shutdown);
+  process.on("SIGINT", shutdown);
+
+  fs.rmSync(socketPath, { force: true });
+  srv.listen(socketPath);
+  resetIdleTimer();
+}
+
+async function runner(entry) {
+  // Listen first so a request arriving during warm-up is not lost
+  const nextRequest = new Promise((resolve) => process.once("message", resolve));
+
+  // Warm up before the request arrives: enable the compile cache and load
+  // openclaw's dependencies so that only openclaw's own modules remain
+  module.enableCompileCache?.();
+  const require = createRequire(entry);
+  try {
+    const pkg = JSON.parse(fs.readFileSync(path.join(path.dirname(entry), "package.json"), "utf8"));
+    for (const dependency of Object.keys(pkg.dependencies ?? {})) {
+      try {
+        await import(pathToFileURL(require.resolve(dependency)).href);
+      } catch {
+        // Dependencies that cannot be preloaded are loaded by openclaw itself
+      }
+    }
+  } catch {
+    // Missing package.json: nothing to preload
+  }
+
+  const request = await nextRequest;
+  process.argv = [process.execPath, entry, ...request.argv];
+  for (const key of Object.keys(process.env)) delete process.env[key];
+  Object.assign(process.env, request.env);
+  process.chdir(request.cwd);
+  // Drop the IPC channel so the process exits once openclaw is done
+  process.disconnect();
+  await import(pathToFileURL(entry).href);
+}
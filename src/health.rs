@@ -0,0 +1,457 @@
+//! Native `health` - probes the OpenClaw gateway's HTTP health endpoint
+//! directly, with connect and read timeouts, instead of starting Node.js.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::native::{Flag, NativeCommand, Outcome};
+use crate::openclaw_config;
+
+/// Default time allowed for connecting and for the response
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Default time between probes with `--watch`
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Largest response read from the gateway
+const MAX_RESPONSE: u64 = 64 * 1024;
+
+/// Gateway endpoint to probe
+#[derive(Debug, PartialEq)]
+struct Target {
+    host: String,
+    port: u16,
+    path: String,
+    token: Option<String>,
+}
+
+impl Target {
+    /// host:port, with IPv6 addresses in brackets
+    fn authority(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    fn url(&self) -> String {
+        format!("http://{}{}", self.authority(), self.path)
+    }
+}
+
+/// Result of one probe
+#[derive(Serialize)]
+struct Probe {
+    url: String,
+    ok: bool,
+    /// HTTP status code, if a response was received
+    status: Option<u16>,
+    latency_ms: u64,
+    error: Option<String>,
+    /// Response body, when it is JSON
+    body: Option<Value>,
+}
+
+/// Work out the endpoint from openclaw's config: the local gateway on
+/// `gateway.port` at the host `gateway.bind` resolves to, or
+/// `gateway.remote.url` in remote mode. `--host`/`--port` override the local
+/// endpoint. Returns None for endpoints left to openclaw (TLS, tailnet binds).
+fn target(config: Option<&Value>, host: Option<&str>, port: Option<u16>) -> Option<Target> {
+    let get = |pointer: &str| {
+        config
+            .and_then(|c| c.pointer(pointer))
+            .and_then(Value::as_str)
+            .map(String::from)
+    };
+
+    if host.is_none()
+        && port.is_none()
+        && get("/gateway/mode").as_deref() == Some("remote")
+        && let Some(url) = get("/gateway/remote/url")
+    {
+        let rest = url
+            .strip_prefix("ws://")
+            .or_else(|| url.strip_prefix("http://"))?;
+        let authority = rest.split('/').next().unwrap_or(rest);
+        let (host, port) = match authority.strip_prefix('[') {
+            // IPv6 literal, e.g. [::1]:18789
+            Some(ipv6) => {
+                let (host, rest) = ipv6.split_once(']')?;
+                match rest.strip_prefix(':') {
+                    Some(port) => (host, port.parse().ok()?),
+                    None => (host, 80),
+                }
+            }
+            None => match authority.rsplit_once(':') {
+                Some((host, port)) => (host, port.parse().ok()?),
+                None => (authority, 80),
+            },
+        };
+        return Some(Target {
+            host: host.to_string(),
+            port,
+            path: "/health".to_string(),
+            token: get("/gateway/remote/token"),
+        });
+    }
+
+    Some(Target {
+        host: match host {
+            Some(host) => host.to_string(),
+            None => openclaw_config::gateway_host(config)?,
+        },
+        port: port.unwrap_or_else(|| openclaw_config::gateway_port(config)),
+        path: "/health".to_string(),
+        token: get("/gateway/auth/token"),
+    })
+}
+
+/// Send `GET <path>` and read the response, within `timeout` for the connect
+/// and again for the response
+fn probe(target: &Target, timeout: Duration) -> Probe {
+    let start = Instant::now();
+    let result = request(target, timeout);
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    match result {
+        Ok((status, body)) => Probe {
+            url: target.url(),
+            ok: (200..300).contains(&status),
+            status: Some(status),
+            latency_ms,
+            error: None,
+            body: serde_json::from_str(&body).ok(),
+        },
+        Err(e) => Probe {
+            url: target.url(),
+            ok: false,
+            status: None,
+            latency_ms,
+            error: Some(e.to_string()),
+            body: None,
+        },
+    }
+}
+
+fn request(target: &Target, timeout: Duration) -> Result<(u16, String)> {
+    let addr = (target.host.as_str(), target.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("cannot resolve {}", target.host))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    // HTTP/1.0 keeps the response unchunked and closes the connection after it
+    let mut request = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nAccept: application/json\r\n",
+        target.path,
+        target.authority()
+    );
+    if let Some(token) = &target.token {
+        request.push_str(&format!("Authorization: Bearer {}\r\n", token));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = Vec::new();
+    stream.take(MAX_RESPONSE).read_to_end(&mut response)?;
+    let response = String::from_utf8_lossy(&response);
+
+    let status = response
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("invalid HTTP response"))?;
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_string())
+        .unwrap_or_default();
+    Ok((status, body))
+}
+
+fn describe(probe: &Probe) -> String {
+    match (&probe.status, &probe.error) {
+        (Some(status), _) => format!(
+            "{} {} in {}ms ({})",
+            if probe.ok { "healthy:" } else { "unhealthy:" },
+            status,
+            probe.latency_ms,
+            probe.url
+        ),
+        (None, Some(error)) => format!(
+            "unreachable: {} after {}ms ({})",
+            error, probe.latency_ms, probe.url
+        ),
+        (None, None) => format!("unreachable ({})", probe.url),
+    }
+}
+
+/// Current UTC time of day as HH:MM:SS
+fn clock() -> String {
+    let secs = crate::cache::now() % 86400;
+    format!("{:02}:{:02}:{:02}Z", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+struct Options {
+    json: bool,
+    watch: bool,
+    host: Option<String>,
+    port: Option<u16>,
+    timeout: Duration,
+    interval: Duration,
+}
+
+fn parse_args(args: &[String]) -> Option<Options> {
+    let mut options = Options {
+        json: false,
+        watch: false,
+        host: None,
+        port: None,
+        timeout: DEFAULT_TIMEOUT,
+        interval: DEFAULT_INTERVAL,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--watch" | "-w" => options.watch = true,
+            "--host" => options.host = Some(args.next()?.clone()),
+            "--port" => options.port = Some(args.next()?.parse().ok().filter(|&p| p > 0)?),
+            // Zero would fail every connect or make --watch spin
+            "--timeout" => options.timeout = Duration::from_millis(positive(args.next()?)?),
+            "--interval" => options.interval = Duration::from_secs(positive(args.next()?)?),
+            _ => return None,
+        }
+    }
+    Some(options)
+}
+
+fn positive(value: &str) -> Option<u64> {
+    value.parse().ok().filter(|&n| n > 0)
+}
+
+/// Flags the native handler supports
+const FLAGS: &[Flag] = &[
+    Flag::switch("--json"),
+    Flag::switch("--watch"),
+    Flag::switch("-w"),
+    Flag::value("--host"),
+    Flag::value("--port"),
+    Flag::value("--timeout"),
+    Flag::value("--interval"),
+];
+
+/// `chitin health [--json] [--host <host>] [--port <port>] [--timeout <ms>]
+/// [--watch [--interval <s>]]`,
+/// exiting with 1 if the gateway is unhealthy. TLS endpoints are delegated.
+pub struct HealthCommand;
+
+impl NativeCommand for HealthCommand {
+    fn path(&self) -> &'static [&'static str] {
+        &["health"]
+    }
+
+    fn flags(&self) -> &'static [Flag] {
+        FLAGS
+    }
+
+    fn run(&self, args: &[String]) -> Result<Outcome> {
+        let Some(options) = parse_args(args) else {
+            return Ok(Outcome::Delegate);
+        };
+        // openclaw reports unreadable or invalid configs itself
+        let Ok(config) = openclaw_config::load() else {
+            return Ok(Outcome::Delegate);
+        };
+        let Some(target) = target(config.as_ref(), options.host.as_deref(), options.port) else {
+            return Ok(Outcome::Delegate);
+        };
+        watch(&target, &options)
+    }
+}
+
+fn watch(target: &Target, options: &Options) -> Result<Outcome> {
+    loop {
+        let probe = probe(target, options.timeout);
+        if options.json {
+            // One JSON object per line when watching
+            let json = if options.watch {
+                serde_json::to_string(&probe)?
+            } else {
+                serde_json::to_string_pretty(&probe)?
+            };
+            println!("{}", json);
+        } else if options.watch {
+            println!("{}  gateway {}", clock(), describe(&probe));
+        } else {
+            println!("Gateway {}", describe(&probe));
+        }
+
+        if !options.watch {
+            if !probe.ok {
+                std::process::exit(1);
+            }
+            return Ok(Outcome::Handled);
+        }
+        std::thread::sleep(options.interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    /// Serve one canned HTTP response on a local port, returning the request
+    fn serve(response: &'static str) -> (Target, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let n = stream.read(&mut request).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&request[..n]).to_string()
+        });
+        let target = Target {
+            host: "127.0.0.1".to_string(),
+            port,
+            path: "/health".to_string(),
+            token: Some("secret".to_string()),
+        };
+        (target, handle)
+    }
+
+    #[test]
+    fn test_probe_healthy() {
+        let (target, server) =
+            serve("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"ok\":true}");
+        let probe = probe(&target, DEFAULT_TIMEOUT);
+        let request = server.join().unwrap();
+
+        assert!(probe.ok);
+        assert_eq!(probe.status, Some(200));
+        assert_eq!(probe.body, Some(json!({"ok": true})));
+        assert!(request.starts_with("GET /health HTTP/1.0\r\n"));
+        assert!(request.contains("Authorization: Bearer secret\r\n"));
+    }
+
+    #[test]
+    fn test_probe_unhealthy_status() {
+        let (target, server) = serve("HTTP/1.1 503 Service Unavailable\r\n\r\nstarting");
+        let probe = probe(&target, DEFAULT_TIMEOUT);
+        server.join().unwrap();
+
+        assert!(!probe.ok);
+        assert_eq!(probe.status, Some(503));
+        assert_eq!(probe.body, None);
+    }
+
+    #[test]
+    fn test_probe_read_timeout() {
+        // Accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let target = Target {
+            host: "127.0.0.1".to_string(),
+            port: listener.local_addr().unwrap().port(),
+            path: "/health".to_string(),
+            token: None,
+        };
+        let probe = probe(&target, Duration::from_millis(200));
+
+        assert!(!probe.ok);
+        assert_eq!(probe.status, None);
+        assert!(probe.error.is_some());
+        drop(listener);
+    }
+
+    #[test]
+    fn test_probe_refused() {
+        let port = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let target = Target {
+            host: "127.0.0.1".to_string(),
+            port,
+            path: "/health".to_string(),
+            token: None,
+        };
+        let probe = probe(&target, DEFAULT_TIMEOUT);
+        assert!(!probe.ok);
+        assert!(describe(&probe).starts_with("unreachable:"));
+    }
+
+    #[test]
+    fn test_target_from_config() {
+        let local = target(
+            Some(&json!({"gateway": {"port": 19001, "auth": {"token": "t"}}})),
+            None,
+            None,
+        );
+        assert_eq!(
+            local,
+            Some(Target {
+                host: "127.0.0.1".to_string(),
+                port: 19001,
+                path: "/health".to_string(),
+                token: Some("t".to_string()),
+            })
+        );
+        assert_eq!(
+            target(None, None, None).unwrap().port,
+            openclaw_config::DEFAULT_GATEWAY_PORT
+        );
+
+        let remote = json!({"gateway": {"mode": "remote", "remote": {"url": "ws://gw.lan:18789"}}});
+        let remote = target(Some(&remote), None, None).unwrap();
+        assert_eq!((remote.host.as_str(), remote.port), ("gw.lan", 18789));
+
+        let tls = json!({"gateway": {"mode": "remote", "remote": {"url": "wss://gw.example.com"}}});
+        assert_eq!(target(Some(&tls), None, None), None);
+
+        // The bind setting picks the local host; flags override it
+        let custom = json!({"gateway": {"bind": "custom", "customBindHost": "10.0.0.5"}});
+        assert_eq!(target(Some(&custom), None, None).unwrap().host, "10.0.0.5");
+        let lan = json!({"gateway": {"bind": "lan"}});
+        assert_eq!(target(Some(&lan), None, None).unwrap().host, "127.0.0.1");
+        let tailnet = json!({"gateway": {"bind": "tailnet"}});
+        assert_eq!(target(Some(&tailnet), None, None), None);
+        let flags = target(Some(&tailnet), Some("100.64.0.1"), Some(19000)).unwrap();
+        assert_eq!((flags.host.as_str(), flags.port), ("100.64.0.1", 19000));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args: Vec<String> = ["--watch", "--interval", "5", "--timeout", "500"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let options = parse_args(&args).unwrap();
+        assert!(options.watch);
+        assert_eq!(options.interval, Duration::from_secs(5));
+        assert_eq!(options.timeout, Duration::from_millis(500));
+
+        assert!(parse_args(&["--verbose".to_string()]).is_none());
+        assert!(parse_args(&["--timeout".to_string()]).is_none());
+        assert!(parse_args(&["--timeout".to_string(), "0".to_string()]).is_none());
+        assert!(parse_args(&["--interval".to_string(), "0".to_string()]).is_none());
+
+        let args: Vec<String> = ["--host", "gw.lan", "--port", "19000"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.host.as_deref(), Some("gw.lan"));
+        assert_eq!(options.port, Some(19000));
+        assert!(parse_args(&["--port".to_string(), "70000".to_string()]).is_none());
+    }
+}
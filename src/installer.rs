@@ -1,17 +1,30 @@
 //! Interactive installer module for setting up Node.js runtime and openclaw.
 
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use std::io::{self, BufRead, Write};
 use std::process::Command;
 
+use crate::runtime::NodeMismatch;
+
 /// Package manager choice
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PackageManager {
     Pnpm,
     Npm,
 }
 
 impl PackageManager {
+    /// Parse a package manager name ("pnpm" or "npm")
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "pnpm" => Ok(PackageManager::Pnpm),
+            "npm" => Ok(PackageManager::Npm),
+            other => anyhow::bail!("unknown package manager '{}' (expected pnpm or npm)", other),
+        }
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             PackageManager::Pnpm => "pnpm",
@@ -27,8 +40,13 @@ impl PackageManager {
     }
 }
 
-/// Prompt user to select a package manager
+/// Prompt user to select a package manager, unless one is configured
 pub fn prompt_package_manager_selection() -> Result<PackageManager> {
+    if let Some(pm) = crate::config::get().package_manager {
+        println!("Using {} (from chitin config).", pm.name());
+        return Ok(pm);
+    }
+
     println!();
     println!("Select a package manager to install openclaw:");
     println!();
@@ -164,40 +182,88 @@ pub fn install_openclaw(pm: PackageManager) -> Result<()> {
     Ok(())
 }
 
-/// Pre-cache the help output after installation
+/// Pre-cache help for every command after installation
 pub fn precache_help() -> Result<()> {
     println!();
     println!("Pre-caching help output...");
 
-    let shim_path = match which::which("openclaw") {
-        Ok(p) => p,
-        Err(_) => {
-            println!("Note: Could not find openclaw. Help will be cached on first use.");
-            return Ok(());
-        }
-    };
+    // Covers both the shim on PATH and configured openclaw_paths
+    if crate::openclaw::command().is_err() {
+        println!("Note: Could not find openclaw. Help will be cached on first use.");
+        return Ok(());
+    }
 
     let cache = crate::cache::HelpCache::new()?;
 
-    // Cache main help only (subcommands cached on first use)
-    let output = Command::new(&shim_path)
-        .arg("--help")
-        .output()
-        .context("Failed to run openclaw --help for caching")?;
+    // Detect afresh: the memoized version predates this installation
+    let version = crate::openclaw::detect_version();
+    let version = version.as_deref().unwrap_or(crate::OPENCLAW_VERSION);
+    let report = crate::prefetch::prefetch_all(&cache, version, crate::prefetch::DEFAULT_JOBS)?;
 
-    if !output.status.success() {
+    if report.cached == 0 {
         println!("Note: Could not pre-cache help. It will be cached on first use.");
         return Ok(());
     }
 
-    let help_text = String::from_utf8_lossy(&output.stdout).to_string();
-    let rebranded = crate::rebrand_help(&help_text);
-    cache.save_help(&rebranded, crate::OPENCLAW_VERSION, crate::CHITIN_VERSION)?;
-
-    println!("Help cached successfully.");
+    println!("Help cached for {} commands.", report.cached);
     Ok(())
 }
 
+/// Explain that the Node.js running openclaw doesn't satisfy its requirement and
+/// offer to install a supported version with pnpm. Exits rather than returning
+/// to run openclaw on the wrong version.
+pub fn run_node_upgrade(mismatch: &NodeMismatch) -> Result<()> {
+    eprintln!("chitin: {}", mismatch);
+    eprintln!();
+
+    let Some(major) = mismatch.upgrade_major() else {
+        match mismatch.manager {
+            Some(manager) => eprintln!(
+                "Install a Node.js version matching {} and select it with {}.",
+                mismatch.required,
+                manager.name()
+            ),
+            None => eprintln!(
+                "Install a Node.js version matching {} and put it first on PATH.",
+                mismatch.required
+            ),
+        }
+        std::process::exit(1);
+    };
+
+    let has_pnpm = crate::runtime::RuntimeDetector::new().has_pnpm();
+    if is_interactive()
+        && has_pnpm
+        && prompt_confirm(&format!("Install Node.js {} with pnpm now?", major), true)?
+    {
+        let status = Command::new("pnpm")
+            .args(["env", "use", "--global", &major.to_string()])
+            .status()
+            .context("Failed to install Node.js via pnpm")?;
+        if !status.success() {
+            anyhow::bail!("Node.js installation via pnpm failed");
+        }
+        println!();
+        println!(
+            "Node.js {} installed. Run the command again to use it.",
+            major
+        );
+        std::process::exit(0);
+    }
+
+    eprintln!("Upgrade Node.js with one of:");
+    if has_pnpm {
+        eprintln!("  pnpm env use --global {}", major);
+    }
+    eprintln!("  nvm install {}", major);
+    eprintln!(
+        "  curl -fsSL https://deb.nodesource.com/setup_{}.x | sudo -E bash -   # Debian/Ubuntu",
+        major
+    );
+    eprintln!("  brew install node@{}   # macOS", major);
+    std::process::exit(1);
+}
+
 /// Run the full interactive installation flow
 pub fn run_interactive_install() -> Result<()> {
     println!();
@@ -224,19 +290,12 @@ pub fn run_interactive_install() -> Result<()> {
     }
 
     // Check what's already installed
-    let has_node = which::which("node").is_ok();
-    let has_pnpm = which::which("pnpm").is_ok();
-    let has_npm = which::which("npm").is_ok();
+    let detector = crate::runtime::RuntimeDetector::new();
+    let has_node = detector.has_node();
 
-    if has_node && (has_pnpm || has_npm) {
+    if let (true, Some(pm)) = (has_node, detector.preferred_package_manager()) {
         // Node and a package manager exist, just need to install openclaw
-        let pm = if has_pnpm {
-            println!("Found Node.js and pnpm installed.");
-            PackageManager::Pnpm
-        } else {
-            println!("Found Node.js and npm installed.");
-            PackageManager::Npm
-        };
+        println!("Found Node.js and {} installed.", pm.name());
 
         if prompt_confirm("Install openclaw now?", true)? {
             install_openclaw(pm)?;
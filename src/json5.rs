@@ -0,0 +1,751 @@
+//! JSON5 module - parses JSON5 documents while keeping the byte span of every
+//! value, so single values can be edited in place without disturbing the
+//! surrounding formatting and comments.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::ops::Range;
+
+/// A parsed value with its location in the source text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub kind: Kind,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Null,
+    Bool(bool),
+    /// Number as written (e.g. "0x1F", "+.5", "Infinity")
+    Number(String),
+    String(String),
+    Array(Vec<Node>),
+    Object(Vec<Member>),
+}
+
+/// An object member; `span` covers the key through the end of the value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub key: String,
+    pub span: Range<usize>,
+    pub value: Node,
+}
+
+impl Node {
+    /// Look up a member of an object node
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        match &self.kind {
+            Kind::Object(members) => members.iter().find(|m| m.key == key).map(|m| &m.value),
+            _ => None,
+        }
+    }
+
+    /// Convert to a JSON value
+    pub fn to_value(&self) -> Value {
+        match &self.kind {
+            Kind::Null => Value::Null,
+            Kind::Bool(b) => Value::Bool(*b),
+            Kind::Number(raw) => number_value(raw),
+            Kind::String(s) => Value::String(s.clone()),
+            Kind::Array(items) => Value::Array(items.iter().map(Node::to_value).collect()),
+            Kind::Object(members) => Value::Object(
+                members
+                    .iter()
+                    .map(|m| (m.key.clone(), m.value.to_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn number_value(raw: &str) -> Value {
+    let (negative, digits) = match raw.as_bytes().first() {
+        Some(b'-') => (true, &raw[1..]),
+        Some(b'+') => (false, &raw[1..]),
+        _ => (false, raw),
+    };
+    let sign = if negative { -1 } else { 1 };
+
+    if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        return i64::from_str_radix(hex, 16)
+            .map(|n| Value::from(sign * n))
+            .unwrap_or(Value::Null);
+    }
+    if let Ok(n) = digits.parse::<i64>() {
+        return Value::from(sign * n);
+    }
+    // Non-finite numbers (Infinity, NaN) have no JSON form and become null
+    digits
+        .parse::<f64>()
+        .map(|n| Value::from(sign as f64 * n))
+        .unwrap_or(Value::Null)
+}
+
+/// Parse a JSON5 document
+pub fn parse(text: &str) -> Result<Node> {
+    let mut parser = Parser { text, pos: 0 };
+    parser.skip_trivia()?;
+    let node = parser.value()?;
+    parser.skip_trivia()?;
+    if parser.pos < text.len() {
+        return Err(parser.error("unexpected content after value"));
+    }
+    Ok(node)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> anyhow::Error {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        let column = self.pos - self.text[..self.pos].rfind('\n').map_or(0, |i| i + 1) + 1;
+        anyhow::anyhow!("{} at line {}, column {}", message, line, column)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skip whitespace and comments
+    fn skip_trivia(&mut self) -> Result<()> {
+        loop {
+            let rest = &self.text[self.pos..];
+            if rest.starts_with("//") {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                let end = comment
+                    .find("*/")
+                    .ok_or_else(|| self.error("unterminated comment"))?;
+                self.pos += end + 4;
+            } else if let Some(c) = self
+                .peek()
+                .filter(|c| c.is_whitespace() || *c == '\u{feff}')
+            {
+                self.pos += c.len_utf8();
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        let start = self.pos;
+        let kind = match self.peek() {
+            Some('{') => self.object()?,
+            Some('[') => self.array()?,
+            Some('"') | Some('\'') => Kind::String(self.string()?),
+            Some(c) if c == '-' || c == '+' || c == '.' || c.is_ascii_digit() => self.number()?,
+            Some(c) if is_identifier_start(c) => match self.identifier().as_str() {
+                "null" => Kind::Null,
+                "true" => Kind::Bool(true),
+                "false" => Kind::Bool(false),
+                "Infinity" | "NaN" => Kind::Number(self.text[start..self.pos].to_string()),
+                _ => {
+                    self.pos = start;
+                    return Err(self.error("unexpected identifier"));
+                }
+            },
+            Some(_) => return Err(self.error("unexpected character")),
+            None => return Err(self.error("unexpected end of input")),
+        };
+        Ok(Node {
+            kind,
+            span: start..self.pos,
+        })
+    }
+
+    fn object(&mut self) -> Result<Kind> {
+        self.bump();
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.eat('}') {
+                return Ok(Kind::Object(members));
+            }
+
+            let start = self.pos;
+            let key = match self.peek() {
+                Some('"') | Some('\'') => self.string()?,
+                Some(c) if is_identifier_start(c) => self.identifier(),
+                _ => return Err(self.error("expected property name")),
+            };
+            self.skip_trivia()?;
+            if !self.eat(':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.skip_trivia()?;
+            let value = self.value()?;
+            members.push(Member {
+                key,
+                span: start..value.span.end,
+                value,
+            });
+
+            self.skip_trivia()?;
+            if !self.eat(',') {
+                self.skip_trivia()?;
+                if self.eat('}') {
+                    return Ok(Kind::Object(members));
+                }
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Kind> {
+        self.bump();
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.eat(']') {
+                return Ok(Kind::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_trivia()?;
+            if !self.eat(',') {
+                self.skip_trivia()?;
+                if self.eat(']') {
+                    return Ok(Kind::Array(items));
+                }
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(is_identifier_char) {
+            self.bump();
+        }
+        self.text[start..self.pos].to_string()
+    }
+
+    fn number(&mut self) -> Result<Kind> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('+') | Some('-')) {
+            self.bump();
+        }
+        if self.peek().is_some_and(is_identifier_start) {
+            match self.identifier().as_str() {
+                "Infinity" | "NaN" => {}
+                _ => return Err(self.error("invalid number")),
+            }
+        } else {
+            while self
+                .peek()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '+' || c == '-')
+            {
+                // A sign is only part of the number right after an exponent
+                let c = self.peek().unwrap();
+                if (c == '+' || c == '-') && !self.text[..self.pos].ends_with(['e', 'E']) {
+                    break;
+                }
+                self.bump();
+            }
+        }
+
+        let raw = &self.text[start..self.pos];
+        if number_value(raw).is_null() && !raw.ends_with("Infinity") && !raw.ends_with("NaN") {
+            return Err(self.error("invalid number"));
+        }
+        Ok(Kind::Number(raw.to_string()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let quote = self.bump().unwrap();
+        let mut result = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => return Ok(result),
+                Some('\\') => match self.bump() {
+                    Some('n') => result.push('\n'),
+                    Some('t') => result.push('\t'),
+                    Some('r') => result.push('\r'),
+                    Some('b') => result.push('\u{8}'),
+                    Some('f') => result.push('\u{c}'),
+                    Some('v') => result.push('\u{b}'),
+                    Some('0') => result.push('\0'),
+                    Some('x') => result.push(self.hex_escape(2)?),
+                    Some('u') => result.push(self.unicode_escape()?),
+                    // Line continuation
+                    Some('\r') => {
+                        self.eat('\n');
+                    }
+                    Some('\n') | Some('\u{2028}') | Some('\u{2029}') => {}
+                    Some(c) => result.push(c),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) => result.push(c),
+            }
+        }
+    }
+
+    fn hex_digits(&mut self, len: usize) -> Result<u32> {
+        let digits = self.text.get(self.pos..self.pos + len).unwrap_or("");
+        let code = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid escape"))?;
+        self.pos += len;
+        Ok(code)
+    }
+
+    fn hex_escape(&mut self, len: usize) -> Result<char> {
+        let code = self.hex_digits(len)?;
+        char::from_u32(code).ok_or_else(|| self.error("invalid escape"))
+    }
+
+    fn unicode_escape(&mut self) -> Result<char> {
+        let high = self.hex_digits(4)?;
+        if (0xD800..0xDC00).contains(&high) && self.text[self.pos..].starts_with("\\u") {
+            self.pos += 2;
+            let low = self.hex_digits(4)?;
+            let code = 0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+            return char::from_u32(code).ok_or_else(|| self.error("invalid escape"));
+        }
+        char::from_u32(high).ok_or_else(|| self.error("invalid escape"))
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Formatting conventions detected from a document, used for inserted text
+struct Style {
+    /// One level of indentation
+    indent: String,
+    /// Whether object keys are quoted
+    quote_keys: bool,
+}
+
+impl Style {
+    fn detect(text: &str, root: &Node) -> Self {
+        let mut style = Style {
+            indent: "  ".to_string(),
+            quote_keys: true,
+        };
+        if let Kind::Object(members) = &root.kind
+            && let Some(first) = members.first()
+        {
+            let line_indent = indentation_at(text, first.span.start);
+            if !line_indent.is_empty() && line_start(text, first.span.start) != 0 {
+                style.indent = line_indent.to_string();
+            }
+            style.quote_keys = matches!(text[first.span.start..].chars().next(), Some('"'));
+        }
+        style
+    }
+
+    fn key(&self, key: &str) -> String {
+        let bare = key.chars().next().is_some_and(is_identifier_start)
+            && key.chars().all(is_identifier_char);
+        if bare && !self.quote_keys {
+            key.to_string()
+        } else {
+            Value::String(key.to_string()).to_string()
+        }
+    }
+
+    /// Render a value on a single line
+    fn render_inline(&self, value: &Value) -> String {
+        match value {
+            Value::Object(map) => {
+                let members: Vec<String> = map
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", self.key(k), self.render_inline(v)))
+                    .collect();
+                format!("{{{}}}", members.join(", "))
+            }
+            Value::Array(items) => {
+                let items: Vec<String> = items.iter().map(|v| self.render_inline(v)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            _ => value.to_string(),
+        }
+    }
+
+    /// Render a value whose first line starts at `indent`
+    fn render(&self, value: &Value, indent: &str) -> String {
+        let inner = format!("{}{}", indent, self.indent);
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                let members: Vec<String> = map
+                    .iter()
+                    .map(|(k, v)| format!("{}{}: {}", inner, self.key(k), self.render(v, &inner)))
+                    .collect();
+                format!("{{\n{}\n{}}}", members.join(",\n"), indent)
+            }
+            Value::Array(items) if !items.is_empty() => {
+                let scalars = items.iter().all(|v| !v.is_object() && !v.is_array());
+                let rendered: Vec<String> = items.iter().map(|v| self.render(v, &inner)).collect();
+                let one_line = format!("[{}]", rendered.join(", "));
+                if scalars && one_line.len() <= 80 {
+                    one_line
+                } else {
+                    let lines: Vec<String> =
+                        rendered.iter().map(|r| format!("{}{}", inner, r)).collect();
+                    format!("[\n{}\n{}]", lines.join(",\n"), indent)
+                }
+            }
+            _ => value.to_string(),
+        }
+    }
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// Leading whitespace of the line containing `pos`
+fn indentation_at(text: &str, pos: usize) -> &str {
+    let start = line_start(text, pos);
+    let line = &text[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Position after `pos` skipping spaces, tabs and comments on the same line
+fn skip_inline_trivia(text: &str, mut pos: usize) -> usize {
+    loop {
+        let rest = &text[pos..];
+        if rest.starts_with([' ', '\t']) {
+            pos += 1;
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            match comment.find("*/") {
+                Some(end) if !comment[..end].contains('\n') => pos += end + 4,
+                _ => return pos,
+            }
+        } else {
+            return pos;
+        }
+    }
+}
+
+/// A single replacement of `range` with `text`
+struct Edit {
+    range: Range<usize>,
+    text: String,
+}
+
+fn apply(text: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|e| std::cmp::Reverse(e.range.start));
+    let mut result = text.to_string();
+    for edit in edits {
+        result.replace_range(edit.range, &edit.text);
+    }
+    result
+}
+
+/// Set the value at an object key path, creating intermediate objects as
+/// needed. Returns the edited document text.
+pub fn set(text: &str, path: &[&str], value: &Value) -> Result<String> {
+    let root = parse(text)?;
+    let style = Style::detect(text, &root);
+
+    // Descend as far as the path exists
+    let mut node = &root;
+    let mut depth = 0;
+    while depth < path.len() {
+        match node.get(path[depth]) {
+            Some(child) => {
+                node = child;
+                depth += 1;
+            }
+            None => break,
+        }
+    }
+
+    if depth == path.len() {
+        // Replace the existing value in place
+        let indent = indentation_at(text, node.span.start);
+        let rendered = style.render(value, indent);
+        return Ok(apply(
+            text,
+            vec![Edit {
+                range: node.span.clone(),
+                text: rendered,
+            }],
+        ));
+    }
+
+    let Kind::Object(members) = &node.kind else {
+        anyhow::bail!("{} is not an object", path[..depth].join("."));
+    };
+
+    // Wrap the value in objects for the missing part of the path
+    let mut new_value = value.clone();
+    for key in path[depth + 1..].iter().rev() {
+        let mut map = serde_json::Map::new();
+        map.insert(key.to_string(), new_value);
+        new_value = Value::Object(map);
+    }
+    let key = style.key(path[depth]);
+
+    let edits = match members.last() {
+        Some(last) => {
+            let indent = indentation_at(text, last.span.start);
+
+            // Insert after the last member (and its comma and comments on the same line)
+            let after_value = skip_inline_trivia(text, last.span.end);
+            let has_comma = text[after_value..].starts_with(',');
+            let anchor = if has_comma {
+                after_value + 1
+            } else {
+                last.span.end
+            };
+            let line_end = line_end_after(text, anchor);
+
+            if line_end == anchor && !text[anchor..].starts_with(['\n', '\r']) {
+                // The object continues on this line: keep the new member inline
+                let member = format!("{}: {}", key, style.render_inline(&new_value));
+                let member = if has_comma {
+                    format!(" {},", member)
+                } else {
+                    format!(", {}", member)
+                };
+                vec![Edit {
+                    range: anchor..anchor,
+                    text: member,
+                }]
+            } else {
+                let member = format!("{}: {}", key, style.render(&new_value, indent));
+                let mut edits = vec![Edit {
+                    range: line_end..line_end,
+                    text: format!("\n{}{}{}", indent, member, if has_comma { "," } else { "" }),
+                }];
+                if !has_comma {
+                    edits.push(Edit {
+                        range: last.span.end..last.span.end,
+                        text: ",".to_string(),
+                    });
+                }
+                edits
+            }
+        }
+        None => {
+            // Empty object: put the member on its own line
+            let outer = indentation_at(text, node.span.start);
+            let indent = format!("{}{}", outer, style.indent);
+            let member = format!("{}: {}", key, style.render(&new_value, &indent));
+            vec![Edit {
+                range: node.span.start + 1..node.span.end - 1,
+                text: format!("\n{}{}\n{}", indent, member, outer),
+            }]
+        }
+    };
+
+    Ok(apply(text, edits))
+}
+
+/// End of the line at `pos` if only whitespace and comments follow on it,
+/// otherwise `pos` itself
+fn line_end_after(text: &str, pos: usize) -> usize {
+    let end = skip_inline_trivia(text, pos);
+    let rest = &text[end..];
+    if rest.starts_with("//") {
+        end + rest.find('\n').unwrap_or(rest.len())
+    } else if rest.starts_with(['\n', '\r']) {
+        end
+    } else {
+        pos
+    }
+}
+
+/// Remove the member at an object key path. Returns None if it doesn't exist.
+pub fn unset(text: &str, path: &[&str]) -> Result<Option<String>> {
+    let root = parse(text)?;
+    let Some((key, parent_path)) = path.split_last() else {
+        return Ok(None);
+    };
+
+    let mut parent = &root;
+    for segment in parent_path {
+        match parent.get(segment) {
+            Some(child) => parent = child,
+            None => return Ok(None),
+        }
+    }
+    let Kind::Object(members) = &parent.kind else {
+        return Ok(None);
+    };
+    let Some(index) = members.iter().position(|m| m.key == *key) else {
+        return Ok(None);
+    };
+    let member = &members[index];
+
+    // Extend over the member's comma, if any
+    let after_value = skip_inline_trivia(text, member.span.end);
+    let has_comma = text[after_value..].starts_with(',');
+    let mut end = if has_comma {
+        after_value + 1
+    } else {
+        member.span.end
+    };
+    let mut start = member.span.start;
+
+    // Remove whole lines when the member sits on lines of its own
+    let start_of_line = line_start(text, start);
+    let line_end = line_end_after(text, end);
+    let rest = &text[line_end..];
+    if text[start_of_line..start].trim().is_empty()
+        && (rest.is_empty() || rest.starts_with(['\n', '\r']))
+    {
+        start = start_of_line;
+        end = line_end + rest.find('\n').map_or(rest.len(), |i| i + 1);
+    } else if has_comma {
+        end = skip_inline_trivia(text, end);
+    } else {
+        start = text[..start].trim_end_matches([' ', '\t']).len();
+    }
+
+    let mut edits = vec![Edit {
+        range: start..end,
+        text: String::new(),
+    }];
+
+    // Removing the last member leaves a dangling comma on the previous one
+    if !has_comma && index > 0 {
+        let previous = &members[index - 1];
+        let comma = skip_inline_trivia(text, previous.span.end);
+        if text[comma..].starts_with(',') && comma < start {
+            edits.push(Edit {
+                range: comma..comma + 1,
+                text: String::new(),
+            });
+        }
+    }
+
+    Ok(Some(apply(text, edits)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CONFIG: &str = r#"// OpenClaw config
+{
+  gateway: {
+    port: 18789, // default port
+    mode: 'local',
+  },
+  /* channels */
+  channels: {
+    whatsapp: { allowFrom: ["+15555550123"] },
+  },
+}
+"#;
+
+    #[test]
+    fn test_parse_json5() {
+        let root = parse(CONFIG).unwrap();
+        assert_eq!(
+            root.to_value(),
+            json!({
+                "gateway": {"port": 18789, "mode": "local"},
+                "channels": {"whatsapp": {"allowFrom": ["+15555550123"]}},
+            })
+        );
+
+        let port = root.get("gateway").unwrap().get("port").unwrap();
+        assert_eq!(&CONFIG[port.span.clone()], "18789");
+
+        let values = parse(r#"{a: 0x1F, b: -.5, c: +1e3, d: 'it\'s', e: "é\x41", f: null}"#)
+            .unwrap()
+            .to_value();
+        assert_eq!(
+            values,
+            json!({"a": 31, "b": -0.5, "c": 1000.0, "d": "it's", "e": "éA", "f": null})
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("{a: }").is_err());
+        assert!(parse("{a: 1} x").is_err());
+        assert!(parse("{a: 'open").is_err());
+        assert!(parse("/* never closed").is_err());
+        let error = parse("{\n  a: nope\n}").unwrap_err().to_string();
+        assert!(error.contains("line 2"), "{}", error);
+    }
+
+    #[test]
+    fn test_set_existing_preserves_comments() {
+        let edited = set(CONFIG, &["gateway", "port"], &json!(19001)).unwrap();
+        assert_eq!(edited, CONFIG.replace("18789", "19001"));
+    }
+
+    #[test]
+    fn test_set_new_keys() {
+        let edited = set(CONFIG, &["gateway", "auth", "token"], &json!("secret")).unwrap();
+        assert!(
+            edited
+                .contains("    mode: 'local',\n    auth: {\n      token: \"secret\"\n    },\n  },"),
+            "{}",
+            edited
+        );
+        assert!(edited.starts_with("// OpenClaw config\n"));
+        assert!(parse(&edited).is_ok());
+
+        // Strict JSON without trailing commas
+        let json = "{\n  \"gateway\": {\n    \"port\": 18789\n  }\n}\n";
+        let edited = set(json, &["gateway", "mode"], &json!("remote")).unwrap();
+        assert_eq!(
+            edited,
+            "{\n  \"gateway\": {\n    \"port\": 18789,\n    \"mode\": \"remote\"\n  }\n}\n"
+        );
+
+        let edited = set("{}", &["logging", "level"], &json!("debug")).unwrap();
+        assert_eq!(
+            edited,
+            "{\n  \"logging\": {\n    \"level\": \"debug\"\n  }\n}"
+        );
+        assert_eq!(
+            set("{a: 1}", &["b", "c"], &json!(true)).unwrap(),
+            "{a: 1, b: {c: true}}"
+        );
+    }
+
+    #[test]
+    fn test_set_through_non_object_fails() {
+        assert!(set(CONFIG, &["gateway", "port", "x"], &json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_unset() {
+        let edited = unset(CONFIG, &["gateway", "mode"]).unwrap().unwrap();
+        assert_eq!(edited, CONFIG.replace("    mode: 'local',\n", ""));
+
+        let json = "{\n  \"gateway\": {\n    \"port\": 18789,\n    \"mode\": \"local\"\n  }\n}\n";
+        let edited = unset(json, &["gateway", "mode"]).unwrap().unwrap();
+        assert_eq!(edited, "{\n  \"gateway\": {\n    \"port\": 18789\n  }\n}\n");
+
+        assert_eq!(unset("{a: 1, b: 2}", &["a"]).unwrap().unwrap(), "{b: 2}");
+        assert_eq!(unset("{a: 1, b: 2}", &["b"]).unwrap().unwrap(), "{a: 1}");
+        assert_eq!(unset(CONFIG, &["gateway", "bind"]).unwrap(), None);
+    }
+}
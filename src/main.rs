@@ -1,29 +1,58 @@
 use anyhow::{Context, Result};
 use clap::Parser;
-use std::path::PathBuf;
 use std::process::Command;
 
 mod cache;
+mod child;
+mod command_tree;
+mod completions;
+mod config;
+#[cfg(unix)]
+mod daemon;
+mod health;
 mod installer;
+mod json5;
+mod native;
+mod node_managers;
+mod openclaw;
+mod openclaw_config;
+mod prefetch;
+mod refresh;
 mod runtime;
+mod sessions;
+mod shim;
+mod status;
 
 use cache::HelpCache;
-use runtime::RuntimeDetector;
+use openclaw::find_openclaw_mjs;
+use runtime::{JsRuntime, RuntimeDetector};
 
+/// OpenClaw version chitin was built against, used when the installed version
+/// cannot be detected
 const OPENCLAW_VERSION: &str = "2026.5.28";
 const CHITIN_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Rebrand help text for chitin CLI
+/// Rebrand help text for chitin CLI, unless disabled with `rebrand = false` or
+/// running as `openclaw` (shim mode)
 /// - Replace version line with chitin version (remove random message)
 /// - Replace "openclaw" with "chitin" in Usage and Examples sections only
 fn rebrand_help(text: &str) -> String {
+    rebrand_help_for(text, openclaw::version())
+}
+
+/// Rebrand help text that came from a specific openclaw version
+fn rebrand_help_for(text: &str, openclaw_version: &str) -> String {
+    if !config::get().rebrand || shim::active() {
+        return text.to_string();
+    }
+
     let mut result = String::new();
     let mut in_examples = false;
 
     for line in text.lines() {
         let rebranded_line = if line.starts_with("🦞 OpenClaw") || line.starts_with("OpenClaw") {
             // Replace version line
-            format!("chitin {} (openclaw {})", CHITIN_VERSION, OPENCLAW_VERSION)
+            format!("chitin {} (openclaw {})", CHITIN_VERSION, openclaw_version)
         } else if line.starts_with("Usage:") {
             // Replace in usage line
             line.replace("openclaw", "chitin")
@@ -81,52 +110,118 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // chitin's own commands, which openclaw's would shadow in shim mode
+    match cli.args.first().map(|s| s.as_str()) {
+        Some("__complete") => return completions::run(&cli.args[1..]),
+        Some("__refresh") => return refresh::run(),
+        _ if shim::active() => {}
+        Some("cache") => return cache::command(&cli.args[1..]),
+        Some("completions") => return completions::print_script(&cli.args[1..]),
+        Some("shim") => return shim::command(&cli.args[1..]),
+        #[cfg(unix)]
+        Some("daemon") => return daemon::command(&cli.args[1..]),
+        _ => {}
+    }
+
     if cli.help || cli.args.is_empty() {
         return print_help();
     }
 
+    // Commands implemented natively, unless they need openclaw. In shim mode
+    // output and exit codes must be openclaw's own, so everything is delegated
+    if !shim::active() && native::dispatch(&cli.args)? {
+        return Ok(());
+    }
+
     // Pass through to Node.js openclaw for all other commands
     delegate_to_node(&cli.args)
 }
 
 fn print_version() {
-    println!("openclaw {}", OPENCLAW_VERSION);
+    // openclaw prints just its version
+    if shim::active() {
+        println!("{}", openclaw::version());
+        return;
+    }
+    println!("openclaw {}", openclaw::version());
     println!("chitin {}", CHITIN_VERSION);
 }
 
 fn print_help() -> Result<()> {
     let cache = HelpCache::new()?;
 
-    // Try to use cached help first
-    if let Some(help_text) = cache.get_cached_help(OPENCLAW_VERSION, CHITIN_VERSION)? {
-        print!("{}", help_text);
+    // Try to use cached help first, refreshing expired help in the background
+    if show_cached_help(&cache, "")? {
         return Ok(());
     }
 
     // Need to generate help from Node.js
     let detector = RuntimeDetector::new();
 
-    if !detector.has_node() {
+    if !detector.has_runtime() {
+        if show_offline_help(&cache, "")? {
+            return Ok(());
+        }
         return prompt_install_runtime();
     }
+    check_node_version(&detector)?;
 
     // Run the Node.js openclaw to get help
-    let help_text = run_node_help()?;
+    let help_text = run_node_help(&[])?;
 
-    // Rebrand and cache for next time
-    let rebranded = rebrand_help(&help_text);
-    cache.save_help(&rebranded, OPENCLAW_VERSION, CHITIN_VERSION)?;
+    // Cache as printed by openclaw; rebranding is applied on display
+    cache.save_help(&help_text, openclaw::version(), CHITIN_VERSION)?;
 
-    print!("{}", rebranded);
+    print!("{}", rebrand_help(&help_text));
     Ok(())
 }
 
-fn run_node_help() -> Result<String> {
-    // Try to run the openclaw shim directly first (handles pnpm/npm shims)
-    if let Ok(shim_path) = which::which("openclaw") {
-        let output = Command::new(&shim_path)
-            .arg("--help")
-            .output()
+/// Print help cached for the installed openclaw, refreshing it in the
+/// background once expired. Returns false if there is none.
+fn show_cached_help(cache: &HelpCache, command_path: &str) -> Result<bool> {
+    let Some(cached) = cache.lookup_help(command_path, openclaw::version(), CHITIN_VERSION)? else {
+        return Ok(false);
+    };
+
+    if cached.stale {
+        if RuntimeDetector::new().has_runtime() {
+            let _ = refresh::spawn_background(cache);
+        } else {
+            eprintln!("chitin: no Node.js or Bun to run openclaw; cached help may be out of date");
+        }
+    }
+    print!("{}", rebrand_help(&cached.help));
+    Ok(true)
+}
+
+/// Without a runtime, print help cached for any openclaw version rather than
+/// nothing, with a warning. Returns false if there is none.
+fn show_offline_help(cache: &HelpCache, command_path: &str) -> Result<bool> {
+    let Some((help, version)) = cache.lookup_help_any_version(command_path)? else {
+        return Ok(false);
+    };
+
+    eprintln!(
+        "chitin: no Node.js or Bun to run openclaw; showing cached help for openclaw {}",
+        version
+    );
+    print!("{}", rebrand_help_for(&help, &version));
+    Ok(true)
+}
+
+/// Run `openclaw <command_path> --help` and return its output
+fn run_node_help(command_path: &[String]) -> Result<String> {
+    let runtime = runtime::selected().unwrap_or(JsRuntime::Node);
+
+    // Try to run the openclaw shim directly first (handles pnpm/npm shims).
+    // The shim starts node, so Bun skips it.
+    if runtime == JsRuntime::Node
+        && let Some(shim_path) = openclaw::find_shim()
+    {
+        let mut command = Command::new(&shim_path);
+        runtime::expose_node(&mut command);
+        openclaw::guard(&mut command)?;
+        let output = child::output(command.args(command_path).arg("--help"))
             .context("Failed to run openclaw --help")?;
 
         if output.status.success() {
@@ -134,13 +229,15 @@ fn run_node_help() -> Result<String> {
         }
     }
 
-    // Fallback: find the .mjs file and run with node
-    let openclaw_mjs = find_openclaw_mjs()?;
+    // Fallback: find the .mjs file and run it with the runtime
+    let openclaw_mjs = match runtime {
+        JsRuntime::Node => find_openclaw_mjs()?,
+        JsRuntime::Bun => openclaw::find_entry()?,
+    };
 
-    let output = Command::new("node")
-        .arg(&openclaw_mjs)
-        .arg("--help")
-        .output()
+    let mut command = runtime::command(runtime);
+    openclaw::guard(&mut command)?;
+    let output = child::output(command.arg(&openclaw_mjs).args(command_path).arg("--help"))
         .context("Failed to run openclaw --help")?;
 
     if !output.status.success() {
@@ -151,63 +248,9 @@ fn run_node_help() -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
-/// Find the openclaw.mjs entry point file
-fn find_openclaw_mjs() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Cannot find home directory")?;
-
-    // pnpm global - search for the package in .pnpm store
-    let pnpm_global_dir = home.join(".local/share/pnpm/global/5/.pnpm");
-    if let Ok(entries) = std::fs::read_dir(&pnpm_global_dir) {
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            if name.to_string_lossy().starts_with("openclaw@") {
-                let mjs_path = entry
-                    .path()
-                    .join("node_modules")
-                    .join("openclaw")
-                    .join("openclaw.mjs");
-                if mjs_path.exists() {
-                    return Ok(mjs_path);
-                }
-            }
-        }
-    }
-
-    // pnpm global (older layout)
-    let pnpm_global = home.join(".local/share/pnpm/global/5/node_modules/openclaw/openclaw.mjs");
-    if pnpm_global.exists() {
-        return Ok(pnpm_global);
-    }
-
-    // npm global (Linux system)
-    let npm_global = PathBuf::from("/usr/lib/node_modules/openclaw/openclaw.mjs");
-    if npm_global.exists() {
-        return Ok(npm_global);
-    }
-
-    // npm global (user install)
-    let npm_user = home.join(".npm-global/lib/node_modules/openclaw/openclaw.mjs");
-    if npm_user.exists() {
-        return Ok(npm_user);
-    }
-
-    // npm prefix-based global
-    let npm_prefix = home.join("node_modules/openclaw/openclaw.mjs");
-    if npm_prefix.exists() {
-        return Ok(npm_prefix);
-    }
-
-    anyhow::bail!("Cannot find openclaw installation. Run 'openclaw' without arguments to install.")
-}
-
 fn delegate_to_node(args: &[String]) -> Result<()> {
-    let detector = RuntimeDetector::new();
-
-    if !detector.has_node() {
-        return prompt_install_runtime();
-    }
-
-    // Check if this is a help request for a subcommand
+    // Help requests are answered from the cache when possible, so they are
+    // handled before checking for Node.js
     let is_help_request = args.iter().any(|a| a == "--help" || a == "-h");
 
     if is_help_request {
@@ -215,77 +258,112 @@ fn delegate_to_node(args: &[String]) -> Result<()> {
         return run_subcommand_help(args);
     }
 
-    // Try to run the openclaw shim directly first (handles pnpm/npm shims)
-    if let Ok(shim_path) = which::which("openclaw") {
-        let status = Command::new(&shim_path)
-            .args(args)
-            .status()
-            .context("Failed to run openclaw")?;
+    let detector = RuntimeDetector::new();
+
+    if !detector.has_runtime() {
+        return prompt_install_runtime();
+    }
+    check_node_version(&detector)?;
 
-        std::process::exit(status.code().unwrap_or(1));
+    // Use the warm daemon when one is running (it is a Node.js worker)
+    #[cfg(unix)]
+    if detector.runtime() == Some(JsRuntime::Node)
+        && let Some(status) = daemon::run(args)?
+    {
+        child::exit(status);
     }
 
-    // Fallback: find the .mjs file and run with node
-    let openclaw_mjs = find_openclaw_mjs()?;
+    // Run the openclaw shim (handles pnpm/npm shims), or node or bun on openclaw.mjs
+    let mut command = openclaw::command()?;
+    command.args(args);
+    exec(command)
+}
 
-    let status = Command::new("node")
-        .arg(&openclaw_mjs)
-        .args(args)
-        .status()
-        .context("Failed to run openclaw")?;
+/// Replace chitin with `command`, so openclaw gets the terminal, job control
+/// and signals directly and its exit status reaches the caller unchanged
+#[cfg(unix)]
+fn exec(mut command: Command) -> Result<()> {
+    use std::os::unix::process::CommandExt;
 
-    std::process::exit(status.code().unwrap_or(1));
+    // exec only returns on failure
+    let error = command.exec();
+    Err(error).context("Failed to run openclaw")
 }
 
-fn run_subcommand_help(args: &[String]) -> Result<()> {
-    // Extract subcommand name (first arg that doesn't start with -)
-    let subcommand = args
-        .iter()
-        .find(|a| !a.starts_with('-'))
-        .map(|s| s.as_str())
-        .unwrap_or("");
+/// Run `command` and exit with its status
+#[cfg(not(unix))]
+fn exec(mut command: Command) -> Result<()> {
+    let status = command.status().context("Failed to run openclaw")?;
+    child::exit(status);
+}
 
+fn run_subcommand_help(args: &[String]) -> Result<()> {
     let cache = HelpCache::new()?;
 
-    // Try cache first
-    if let Some(help_text) =
-        cache.get_cached_subcommand_help(subcommand, OPENCLAW_VERSION, CHITIN_VERSION)?
-    {
-        print!("{}", help_text);
+    // Key the cache on the full command path (e.g. "channels login"), using the
+    // cached command tree to tell subcommands apart from option values
+    let command_path = command_tree(&cache)?.command_path(args).join(" ");
+
+    // Try cache first, refreshing expired help in the background
+    if show_cached_help(&cache, &command_path)? {
         return Ok(());
     }
 
+    let detector = RuntimeDetector::new();
+    if !detector.has_runtime() {
+        if show_offline_help(&cache, &command_path)? {
+            return Ok(());
+        }
+        return prompt_install_runtime();
+    }
+    check_node_version(&detector)?;
+
     // Fetch from Node.js
-    let output = if let Ok(shim_path) = which::which("openclaw") {
-        Command::new(&shim_path)
-            .args(args)
-            .output()
-            .context("Failed to run openclaw")?
-    } else {
-        let openclaw_mjs = find_openclaw_mjs()?;
-        Command::new("node")
-            .arg(&openclaw_mjs)
-            .args(args)
-            .output()
-            .context("Failed to run openclaw")?
-    };
+    let mut command = openclaw::command()?;
+    let output = child::output(command.args(args)).context("Failed to run openclaw")?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    // Rebrand and cache
-    let rebranded = rebrand_help(&stdout);
-    if output.status.success() && !rebranded.is_empty() {
-        let _ =
-            cache.save_subcommand_help(subcommand, &rebranded, OPENCLAW_VERSION, CHITIN_VERSION);
+    // Cache as printed by openclaw, then rebrand for display. The usage line
+    // names the command exactly, where the cached tree may have had to guess.
+    if output.status.success() && !stdout.is_empty() {
+        let help = command_tree::parse_help(&stdout);
+        let key = if help.usage.is_empty() {
+            command_path
+        } else {
+            help.usage_path().join(" ")
+        };
+        let _ = cache.save_subcommand_help(&key, &stdout, openclaw::version(), CHITIN_VERSION);
     }
 
-    print!("{}", rebranded);
+    print!("{}", rebrand_help(&stdout));
     eprint!("{}", rebrand_help(&stderr));
 
-    std::process::exit(output.status.code().unwrap_or(1));
+    child::exit(output.status);
+}
+
+/// The cached command tree, preferring the installed openclaw version
+fn command_tree(cache: &HelpCache) -> Result<command_tree::CommandNode> {
+    let tree = match cache.get_command_tree(openclaw::version(), CHITIN_VERSION)? {
+        Some(tree) => Some(tree),
+        None => cache.get_command_tree_any_version()?,
+    };
+    Ok(tree.unwrap_or_default())
 }
 
 fn prompt_install_runtime() -> Result<()> {
     installer::run_interactive_install()
 }
+
+/// Stop before running openclaw on a Node.js version outside its `engines`
+/// range, explaining the mismatch and offering an upgrade
+fn check_node_version(detector: &RuntimeDetector) -> Result<()> {
+    if detector.runtime() != Some(JsRuntime::Node) {
+        return Ok(());
+    }
+    match detector.node_mismatch() {
+        Some(mismatch) => installer::run_node_upgrade(&mismatch),
+        None => Ok(()),
+    }
+}
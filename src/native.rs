@@ -0,0 +1,185 @@
+//! Native command registry - commands chitin implements in Rust, keyed by
+//! command path. Anything a handler doesn't support is delegated to openclaw,
+//! so native coverage can grow without breaking parity.
+
+use anyhow::Result;
+
+/// Result of running a native handler
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The command ran natively
+    Handled,
+    /// The handler can't reproduce openclaw's behavior for these arguments
+    Delegate,
+}
+
+/// A flag a native handler understands
+pub struct Flag {
+    /// Long (`--json`) or short (`-w`) form
+    pub name: &'static str,
+    /// Whether the flag is followed by a value (`--limit 5` or `--limit=5`)
+    pub takes_value: bool,
+}
+
+impl Flag {
+    pub const fn switch(name: &'static str) -> Self {
+        Self {
+            name,
+            takes_value: false,
+        }
+    }
+
+    pub const fn value(name: &'static str) -> Self {
+        Self {
+            name,
+            takes_value: true,
+        }
+    }
+}
+
+/// A command implemented natively
+pub trait NativeCommand: Sync {
+    /// Command path this handler serves, e.g. `["config"]`
+    fn path(&self) -> &'static [&'static str];
+
+    /// Flags the handler supports; any other flag is delegated
+    fn flags(&self) -> &'static [Flag];
+
+    /// Run with the arguments following the command path
+    fn run(&self, args: &[String]) -> Result<Outcome>;
+}
+
+/// Every native handler
+static COMMANDS: &[&dyn NativeCommand] = &[
+    &crate::openclaw_config::ConfigCommand,
+    &crate::health::HealthCommand,
+    &crate::sessions::SessionsCommand,
+    &crate::status::StatusCommand,
+];
+
+/// Find the handler with the longest command path matching the arguments
+fn find<'a>(commands: &[&'a dyn NativeCommand], args: &[String]) -> Option<&'a dyn NativeCommand> {
+    commands
+        .iter()
+        .filter(|c| {
+            let path = c.path();
+            args.len() >= path.len() && path.iter().zip(args).all(|(p, a)| p == a)
+        })
+        .max_by_key(|c| c.path().len())
+        .copied()
+}
+
+/// Check that every flag in `args` is one the handler declares
+fn supports(command: &dyn NativeCommand, args: &[String]) -> bool {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        // "--" isn't a declared flag, so anything using it goes to openclaw
+        if !arg.starts_with('-') || arg == "-" {
+            continue;
+        }
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, _)) => (name, true),
+            None => (arg.as_str(), false),
+        };
+        match command.flags().iter().find(|f| f.name == name) {
+            Some(flag) if flag.takes_value => {
+                if !inline_value && args.next().is_none() {
+                    return false;
+                }
+            }
+            Some(_) if !inline_value => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Split `--flag=value` arguments so handlers only see separate words
+fn split_inline_values(args: &[String]) -> Vec<String> {
+    args.iter()
+        .flat_map(|arg| match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => {
+                vec![name.to_string(), value.to_string()]
+            }
+            _ => vec![arg.clone()],
+        })
+        .collect()
+}
+
+/// Run the command natively if a handler supports it. Returns false when it
+/// must be delegated to openclaw, including when native commands are disabled
+/// (`native = false` or CHITIN_NATIVE=0).
+pub fn dispatch(args: &[String]) -> Result<bool> {
+    if !crate::config::get().native {
+        return Ok(false);
+    }
+    let Some(command) = find(COMMANDS, args) else {
+        return Ok(false);
+    };
+    let rest = &args[command.path().len()..];
+    if !supports(command, rest) {
+        return Ok(false);
+    }
+    Ok(command.run(&split_inline_values(rest))? == Outcome::Handled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAGS: &[Flag] = &[Flag::switch("--json"), Flag::value("--limit")];
+
+    struct Fake(&'static [&'static str]);
+
+    impl NativeCommand for Fake {
+        fn path(&self) -> &'static [&'static str] {
+            self.0
+        }
+
+        fn flags(&self) -> &'static [Flag] {
+            FLAGS
+        }
+
+        fn run(&self, _args: &[String]) -> Result<Outcome> {
+            Ok(Outcome::Handled)
+        }
+    }
+
+    fn args(words: &[&str]) -> Vec<String> {
+        words.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_longest_path() {
+        let commands: &[&dyn NativeCommand] = &[&Fake(&["config"]), &Fake(&["config", "get"])];
+
+        let found = find(commands, &args(&["config", "get", "gateway.port"])).unwrap();
+        assert_eq!(found.path(), &["config", "get"]);
+        let found = find(commands, &args(&["config", "set"])).unwrap();
+        assert_eq!(found.path(), &["config"]);
+        assert!(find(commands, &args(&["gateway"])).is_none());
+        assert!(find(commands, &[]).is_none());
+    }
+
+    #[test]
+    fn test_supported_flags() {
+        let command = Fake(&["sessions"]);
+        assert!(supports(&command, &args(&["--json", "--limit", "5"])));
+        assert!(supports(&command, &args(&["--limit=5", "positional"])));
+
+        assert!(!supports(&command, &args(&["--verbose"])));
+        assert!(!supports(&command, &args(&["--help"])));
+        assert!(!supports(&command, &args(&["-h"])));
+        assert!(!supports(&command, &args(&["--limit"])));
+        assert!(!supports(&command, &args(&["--json=true"])));
+        assert!(!supports(&command, &args(&["--", "x"])));
+    }
+
+    #[test]
+    fn test_split_inline_values() {
+        assert_eq!(
+            split_inline_values(&args(&["--limit=5", "a=b", "--json"])),
+            args(&["--limit", "5", "a=b", "--json"])
+        );
+    }
+}
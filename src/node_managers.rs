@@ -0,0 +1,483 @@
+//! Node.js version managers - finds the node an nvm, fnm, Volta, asdf or mise
+//! installation would run, for shells (cron, IDEs, `sh -c`) that never
+//! sourced the manager's init script.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::runtime::{self, Version};
+
+/// Node.js version managers chitin knows the on-disk layout of
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeManager {
+    Nvm,
+    Fnm,
+    Volta,
+    Asdf,
+    Mise,
+}
+
+/// LTS codenames accepted in `.nvmrc` (`lts/iron`) and their major versions
+const LTS_CODENAMES: &[(&str, u64)] = &[
+    ("argon", 4),
+    ("boron", 6),
+    ("carbon", 8),
+    ("dubnium", 10),
+    ("erbium", 12),
+    ("fermium", 14),
+    ("gallium", 16),
+    ("hydrogen", 18),
+    ("iron", 20),
+    ("jod", 22),
+    ("krypton", 24),
+];
+
+/// A node binary and the version manager that supplied it, if any
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectedNode {
+    pub path: PathBuf,
+    pub manager: Option<NodeManager>,
+    /// Whether `path` is what `node` on PATH runs
+    pub on_path: bool,
+}
+
+impl NodeManager {
+    const ALL: [NodeManager; 5] = [Self::Nvm, Self::Fnm, Self::Volta, Self::Asdf, Self::Mise];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Nvm => "nvm",
+            Self::Fnm => "fnm",
+            Self::Volta => "volta",
+            Self::Asdf => "asdf",
+            Self::Mise => "mise",
+        }
+    }
+
+    /// The manager's data directory, if it exists
+    fn root(&self, var: &impl Fn(&str) -> Option<String>, home: Option<&Path>) -> Option<PathBuf> {
+        let from_home = |relative: &str| home.map(|h| h.join(relative));
+        let xdg_data = var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| from_home(".local/share"));
+
+        let candidates = match self {
+            Self::Nvm => vec![var("NVM_DIR").map(PathBuf::from), from_home(".nvm")],
+            Self::Fnm => vec![
+                var("FNM_DIR").map(PathBuf::from),
+                xdg_data.map(|d| d.join("fnm")),
+                from_home("Library/Application Support/fnm"),
+                from_home(".fnm"),
+            ],
+            Self::Volta => vec![var("VOLTA_HOME").map(PathBuf::from), from_home(".volta")],
+            Self::Asdf => vec![var("ASDF_DATA_DIR").map(PathBuf::from), from_home(".asdf")],
+            Self::Mise => vec![
+                var("MISE_DATA_DIR").map(PathBuf::from),
+                xdg_data.map(|d| d.join("mise")),
+            ],
+        };
+        candidates.into_iter().flatten().find(|dir| dir.is_dir())
+    }
+
+    /// Whether `node` on PATH in `root` is the manager's shim rather than a
+    /// real binary, so the version it runs depends on the directory
+    fn is_shim(&self, root: &Path, node: &Path) -> bool {
+        match self {
+            Self::Volta => node.starts_with(root.join("bin")),
+            Self::Asdf | Self::Mise => node.starts_with(root.join("shims")),
+            Self::Nvm | Self::Fnm => false,
+        }
+    }
+
+    /// Installed Node.js versions and their binaries
+    fn installations(&self, root: &Path) -> Vec<(Version, PathBuf)> {
+        let versions = match self {
+            Self::Nvm => root.join("versions").join("node"),
+            Self::Fnm => root.join("node-versions"),
+            Self::Volta => root.join("tools").join("image").join("node"),
+            Self::Asdf => root.join("installs").join("nodejs"),
+            Self::Mise => root.join("installs").join("node"),
+        };
+        fs::read_dir(versions)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let version = Version::parse(entry.file_name().to_str()?)?;
+                let install = match self {
+                    Self::Fnm => entry.path().join("installation"),
+                    _ => entry.path(),
+                };
+                Some((version, node_binary(&install)))
+            })
+            .filter(|(_, binary)| binary.is_file())
+            .collect()
+    }
+
+    /// The version the manager uses outside any project
+    fn default_spec(
+        &self,
+        root: &Path,
+        var: &impl Fn(&str) -> Option<String>,
+        home: Option<&Path>,
+    ) -> Option<String> {
+        match self {
+            Self::Nvm => {
+                // Aliases can point at other aliases (default -> lts/* -> lts/jod)
+                let mut spec = read_spec(&root.join("alias").join("default"))?;
+                for _ in 0..8 {
+                    match read_spec(&root.join("alias").join(&spec)) {
+                        Some(next) => spec = next,
+                        None => break,
+                    }
+                }
+                Some(spec)
+            }
+            Self::Fnm => {
+                // A link to node-versions/<version>/installation
+                let target = fs::canonicalize(root.join("aliases").join("default")).ok()?;
+                Some(target.parent()?.file_name()?.to_str()?.to_string())
+            }
+            Self::Volta => {
+                let platform = root.join("tools").join("user").join("platform.json");
+                let content = fs::read_to_string(platform).ok()?;
+                let platform: serde_json::Value = serde_json::from_str(&content).ok()?;
+                Some(platform.pointer("/node/runtime")?.as_str()?.to_string())
+            }
+            Self::Asdf => {
+                let name = var("ASDF_DEFAULT_TOOL_VERSIONS_FILENAME")
+                    .unwrap_or_else(|| ".tool-versions".to_string());
+                tool_versions_spec(&home?.join(name))
+            }
+            Self::Mise => {
+                let config = var("XDG_CONFIG_HOME")
+                    .map(PathBuf::from)
+                    .or_else(|| home.map(|h| h.join(".config")))?
+                    .join("mise")
+                    .join("config.toml");
+                let config: toml::Value = fs::read_to_string(config).ok()?.parse().ok()?;
+                let node = config.get("tools")?.get("node")?;
+                let node = match node {
+                    toml::Value::Array(versions) => versions.first()?,
+                    toml::Value::Table(options) => options.get("version")?,
+                    other => other,
+                };
+                Some(node.as_str()?.to_string())
+            }
+        }
+    }
+
+    /// The node binary the manager would run for `project_spec` (or its
+    /// default), choosing the newest matching installed version. Falls back to
+    /// the newest installed version when nothing matches.
+    fn resolve(
+        &self,
+        root: &Path,
+        project_spec: Option<&str>,
+        var: &impl Fn(&str) -> Option<String>,
+        home: Option<&Path>,
+    ) -> Option<PathBuf> {
+        let mut installations = self.installations(root);
+        installations.sort_by_key(|(version, _)| std::cmp::Reverse(*version));
+
+        let default_spec = self.default_spec(root, var, home);
+        [project_spec, default_spec.as_deref()]
+            .into_iter()
+            .flatten()
+            .find_map(|spec| {
+                installations
+                    .iter()
+                    .find(|(version, _)| matches_spec(*version, spec))
+            })
+            .or(installations.first())
+            .map(|(_, binary)| binary.clone())
+    }
+}
+
+/// The node binary in an installation directory
+fn node_binary(install: &Path) -> PathBuf {
+    if cfg!(windows) {
+        install.join("node.exe")
+    } else {
+        install.join("bin").join("node")
+    }
+}
+
+/// First meaningful line of a version file (`.nvmrc`, `.node-version`, an
+/// nvm alias)
+fn read_spec(file: &Path) -> Option<String> {
+    let content = fs::read_to_string(file).ok()?;
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())
+        .map(String::from)
+}
+
+/// The nodejs version in an asdf/mise `.tool-versions` file
+fn tool_versions_spec(file: &Path) -> Option<String> {
+    let content = fs::read_to_string(file).ok()?;
+    content.lines().find_map(|line| {
+        let mut words = line.split('#').next()?.split_whitespace();
+        match words.next()? {
+            "nodejs" | "node" => words.next().map(String::from),
+            _ => None,
+        }
+    })
+}
+
+/// The version requested by the nearest `.nvmrc`, `.node-version` or
+/// `.tool-versions` in `dir` or its ancestors
+fn project_spec(dir: &Path) -> Option<String> {
+    dir.ancestors().find_map(|dir| {
+        read_spec(&dir.join(".nvmrc"))
+            .or_else(|| read_spec(&dir.join(".node-version")))
+            .or_else(|| tool_versions_spec(&dir.join(".tool-versions")))
+    })
+}
+
+/// Whether `version` is what a version file asks for: a version or range
+/// (`22`, `v22.12.0`, `>=20`), `lts/*`, `lts/<codename>`, or `node`/`latest`
+fn matches_spec(version: Version, spec: &str) -> bool {
+    let spec = spec.trim().to_ascii_lowercase();
+    match spec.as_str() {
+        "node" | "latest" | "stable" | "current" => true,
+        // LTS lines are the even majors
+        "lts" | "lts/*" => version.major >= 4 && version.major.is_multiple_of(2),
+        _ => match spec.strip_prefix("lts/") {
+            Some(codename) => LTS_CODENAMES
+                .iter()
+                .any(|&(name, major)| name == codename && major == version.major),
+            None => runtime::satisfies(version, &spec) == Some(true),
+        },
+    }
+}
+
+/// Find node: the one on PATH (`path_node`), or else the one a version
+/// manager would run in `cwd`. Managers are tried in the order of
+/// [`NodeManager::ALL`].
+pub fn detect(
+    var: impl Fn(&str) -> Option<String>,
+    home: Option<&Path>,
+    cwd: Option<&Path>,
+    path_node: Option<PathBuf>,
+) -> Option<DetectedNode> {
+    let roots: Vec<(NodeManager, PathBuf)> = NodeManager::ALL
+        .iter()
+        .filter_map(|m| Some((*m, m.root(&var, home)?)))
+        .collect();
+    let project_spec = cwd.and_then(project_spec);
+
+    if let Some(node) = path_node {
+        let resolved = fs::canonicalize(&node).unwrap_or_else(|_| node.clone());
+        let Some((manager, root)) = roots
+            .iter()
+            .find(|(_, root)| node.starts_with(root) || resolved.starts_with(root))
+        else {
+            return Some(DetectedNode {
+                path: node,
+                manager: None,
+                on_path: true,
+            });
+        };
+
+        // A shim picks the version per directory; use the binary it would
+        // run, so its version is what gets checked
+        let path = manager
+            .is_shim(root, &node)
+            .then(|| manager.resolve(root, project_spec.as_deref(), &var, home))
+            .flatten()
+            .unwrap_or(node);
+        return Some(DetectedNode {
+            path,
+            manager: Some(*manager),
+            on_path: true,
+        });
+    }
+
+    roots.iter().find_map(|(manager, root)| {
+        Some(DetectedNode {
+            path: manager.resolve(root, project_spec.as_deref(), &var, home)?,
+            manager: Some(*manager),
+            on_path: false,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "chitin-node-managers-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Create a fake node binary in an installation directory
+    fn install(dir: &Path) -> PathBuf {
+        let binary = node_binary(dir);
+        fs::create_dir_all(binary.parent().unwrap()).unwrap();
+        fs::write(&binary, "").unwrap();
+        binary
+    }
+
+    fn version(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_matches_spec() {
+        assert!(matches_spec(version("v22.12.0"), "22"));
+        assert!(matches_spec(version("v22.12.0"), "v22.12.0"));
+        assert!(!matches_spec(version("v22.12.0"), "22.11"));
+        assert!(matches_spec(version("v22.12.0"), ">=20"));
+        assert!(matches_spec(version("v22.12.0"), "lts/*"));
+        assert!(!matches_spec(version("v23.1.0"), "lts/*"));
+        assert!(matches_spec(version("v20.20.2"), "lts/iron"));
+        assert!(!matches_spec(version("v22.12.0"), "lts/iron"));
+        assert!(matches_spec(version("v23.1.0"), "node"));
+        assert!(!matches_spec(version("v23.1.0"), "system"));
+    }
+
+    #[test]
+    fn test_project_spec() {
+        let dir = temp_dir("project");
+        let nested = dir.join("packages").join("app");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(project_spec(&nested), None);
+
+        fs::write(dir.join(".tool-versions"), "python 3.12\nnodejs 20.19.0\n").unwrap();
+        assert_eq!(project_spec(&nested), Some("20.19.0".to_string()));
+
+        // The nearest file wins
+        fs::write(dir.join("packages").join(".nvmrc"), "# pinned\nlts/jod\n").unwrap();
+        assert_eq!(project_spec(&nested), Some("lts/jod".to_string()));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_detect_managed() {
+        let home = temp_dir("home");
+        let var = |_: &str| None;
+
+        // Nothing installed
+        assert_eq!(detect(var, Some(&home), None, None), None);
+
+        // nvm: the newest version matching the default alias
+        let nvm = home.join(".nvm");
+        let nvm_20 = install(&nvm.join("versions/node/v20.19.0"));
+        let nvm_22 = install(&nvm.join("versions/node/v22.12.0"));
+        install(&nvm.join("versions/node/v23.3.0"));
+        fs::create_dir_all(nvm.join("alias/lts")).unwrap();
+        fs::write(nvm.join("alias/default"), "lts/*\n").unwrap();
+        fs::write(nvm.join("alias/lts/*"), "lts/jod\n").unwrap();
+        let detected = detect(var, Some(&home), None, None).unwrap();
+        assert_eq!(detected.path, nvm_22);
+        assert_eq!(detected.manager, Some(NodeManager::Nvm));
+        assert!(!detected.on_path);
+
+        // A project's .nvmrc takes precedence over the default
+        let project = home.join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join(".nvmrc"), "20\n").unwrap();
+        let detected = detect(var, Some(&home), Some(&project), None).unwrap();
+        assert_eq!(detected.path, nvm_20);
+
+        // ...unless it asks for a version that isn't installed
+        fs::write(project.join(".nvmrc"), "18\n").unwrap();
+        let detected = detect(var, Some(&home), Some(&project), None).unwrap();
+        assert_eq!(detected.path, nvm_22);
+
+        // node on PATH is used as is, attributed to the manager it belongs to
+        let detected = detect(var, Some(&home), None, Some(nvm_20.clone())).unwrap();
+        assert_eq!(detected.path, nvm_20);
+        assert_eq!(detected.manager, Some(NodeManager::Nvm));
+        assert!(detected.on_path);
+        let system = PathBuf::from("/usr/bin/node");
+        let detected = detect(var, Some(&home), None, Some(system.clone())).unwrap();
+        assert_eq!((detected.path, detected.manager), (system, None));
+
+        let _ = fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn test_manager_defaults() {
+        let home = temp_dir("defaults");
+        let var = |_: &str| None;
+
+        // Volta: the platform default
+        let volta = home.join(".volta");
+        install(&volta.join("tools/image/node/20.19.0"));
+        let volta_22 = install(&volta.join("tools/image/node/22.12.0"));
+        fs::create_dir_all(volta.join("tools/user")).unwrap();
+        fs::write(
+            volta.join("tools/user/platform.json"),
+            r#"{"node": {"runtime": "22.12.0", "npm": null}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            NodeManager::Volta.resolve(&volta, None, &var, Some(&home)),
+            Some(volta_22.clone())
+        );
+
+        // Volta's shim on PATH resolves to the binary it runs
+        let shim = volta.join("bin").join("node");
+        let detected = detect(var, Some(&home), None, Some(shim)).unwrap();
+        assert_eq!(detected.path, volta_22);
+        assert_eq!(detected.manager, Some(NodeManager::Volta));
+
+        // asdf: ~/.tool-versions
+        let asdf = home.join(".asdf");
+        let asdf_20 = install(&asdf.join("installs/nodejs/20.19.0"));
+        install(&asdf.join("installs/nodejs/22.12.0"));
+        fs::write(home.join(".tool-versions"), "nodejs 20.19.0\n").unwrap();
+        assert_eq!(
+            NodeManager::Asdf.resolve(&asdf, None, &var, Some(&home)),
+            Some(asdf_20)
+        );
+
+        // mise: [tools] in its config
+        let mise = home.join(".local/share/mise");
+        install(&mise.join("installs/node/20.19.0"));
+        let mise_22 = install(&mise.join("installs/node/22.12.0"));
+        fs::create_dir_all(home.join(".config/mise")).unwrap();
+        fs::write(
+            home.join(".config/mise/config.toml"),
+            "[tools]\nnode = \"22\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            NodeManager::Mise.resolve(&mise, None, &var, Some(&home)),
+            Some(mise_22)
+        );
+
+        // fnm: the default alias links to an installation
+        #[cfg(unix)]
+        {
+            let fnm = home.join(".local/share/fnm");
+            let fnm_22 = install(&fnm.join("node-versions/v22.12.0/installation"));
+            install(&fnm.join("node-versions/v23.3.0/installation"));
+            fs::create_dir_all(fnm.join("aliases")).unwrap();
+            std::os::unix::fs::symlink(
+                fnm.join("node-versions/v22.12.0/installation"),
+                fnm.join("aliases/default"),
+            )
+            .unwrap();
+            assert_eq!(
+                NodeManager::Fnm.resolve(&fnm, None, &var, Some(&home)),
+                Some(fnm_22)
+            );
+        }
+
+        let _ = fs::remove_dir_all(&home);
+    }
+}
@@ -0,0 +1,575 @@
+//! OpenClaw installation module for locating the Node.js openclaw CLI and
+//! detecting its installed version.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::runtime::JsRuntime;
+
+/// Environment variable counting how many chitin processes are running
+/// openclaw inside each other
+const DEPTH_VAR: &str = "CHITIN_DEPTH";
+
+/// Nesting depth at which chitin assumes it is invoking itself in a loop
+const MAX_DEPTH: u32 = 8;
+
+/// Find the openclaw shim on PATH (installed by npm/pnpm), skipping chitin
+/// itself when it is installed as `openclaw`. Returns None when an openclaw
+/// path is configured, so the configured install is run instead.
+pub fn find_shim() -> Option<PathBuf> {
+    if configured_entry().is_some() {
+        return None;
+    }
+    which::which_all("openclaw")
+        .ok()?
+        .find(|candidate| !is_self(candidate))
+}
+
+/// Whether `path` is the running chitin binary, e.g. through a symlink or hard link
+pub fn is_self(path: &Path) -> bool {
+    static EXE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+    EXE.get_or_init(|| std::env::current_exe().ok())
+        .as_deref()
+        .is_some_and(|exe| same_file(path, exe))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        if let (Ok(a), Ok(b)) = (fs::metadata(a), fs::metadata(b)) {
+            return a.dev() == b.dev() && a.ino() == b.ino();
+        }
+    }
+    matches!(
+        (fs::canonicalize(a), fs::canonicalize(b)),
+        (Ok(a), Ok(b)) if a == b
+    )
+}
+
+/// Depth to pass on to openclaw, given the inherited `CHITIN_DEPTH`
+fn next_depth(inherited: Option<&str>) -> Result<u32> {
+    let depth = inherited.and_then(|d| d.parse::<u32>().ok()).unwrap_or(0);
+    if depth >= MAX_DEPTH {
+        anyhow::bail!(
+            "chitin appears to be running itself as openclaw ({} levels deep). \
+             Make sure the `openclaw` it runs is the Node.js CLI, not a wrapper around chitin.",
+            depth
+        );
+    }
+    Ok(depth + 1)
+}
+
+/// Mark a command that runs openclaw, so a shim that leads back to chitin
+/// fails with a clear error instead of recursing forever
+pub fn guard(command: &mut Command) -> Result<()> {
+    let depth = next_depth(std::env::var(DEPTH_VAR).ok().as_deref())?;
+    command.env(DEPTH_VAR, depth.to_string());
+    Ok(())
+}
+
+/// Command that runs openclaw: bun on its entry point when Bun is the runtime,
+/// otherwise the shim on PATH, or node on openclaw.mjs
+pub fn command() -> Result<Command> {
+    let mut command = match (crate::runtime::selected(), find_shim()) {
+        // The shim would start node, so bun runs the entry point itself
+        (Some(JsRuntime::Bun), _) => {
+            let mut command = crate::runtime::command(JsRuntime::Bun);
+            command.arg(find_entry()?);
+            command
+        }
+        (_, Some(shim)) => {
+            let mut command = Command::new(shim);
+            crate::runtime::expose_node(&mut command);
+            command
+        }
+        (_, None) => {
+            let mut command = crate::runtime::node_command();
+            command.arg(find_openclaw_mjs()?);
+            command
+        }
+    };
+    guard(&mut command)?;
+    Ok(command)
+}
+
+/// First existing entry point among the configured `openclaw_paths`
+fn configured_entry() -> Option<PathBuf> {
+    crate::config::get()
+        .openclaw_paths
+        .iter()
+        .find_map(|path| entry_at(path))
+}
+
+/// Resolve a configured path, which may be openclaw.mjs itself or the package
+/// directory containing it
+fn entry_at(path: &Path) -> Option<PathBuf> {
+    if path.is_dir() {
+        package_entry(path)
+    } else {
+        path.is_file().then(|| path.to_path_buf())
+    }
+}
+
+/// Entry point of an installed openclaw package, from the `bin` field of its
+/// package.json (openclaw.mjs if there is none)
+fn package_entry(package_dir: &Path) -> Option<PathBuf> {
+    let bin = fs::read_to_string(package_dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|package| match package.get("bin")? {
+            serde_json::Value::String(bin) => Some(bin.clone()),
+            serde_json::Value::Object(bins) => bins
+                .get("openclaw")
+                .or_else(|| bins.values().next())?
+                .as_str()
+                .map(String::from),
+            _ => None,
+        });
+
+    let entry = package_dir.join(bin.as_deref().unwrap_or("openclaw.mjs"));
+    entry.is_file().then_some(entry)
+}
+
+/// File in the cache directory remembering where openclaw was found
+const LOCATION_FILE: &str = "openclaw_location";
+
+/// Find the openclaw.mjs entry point file
+pub fn find_openclaw_mjs() -> Result<PathBuf> {
+    // Configured locations take precedence over the built-in ones
+    if let Some(entry) = configured_entry() {
+        return Ok(entry);
+    }
+
+    // Found by an earlier search, as long as it is still installed there
+    let location = crate::cache::cache_dir()
+        .ok()
+        .map(|dir| dir.join(LOCATION_FILE));
+    if let Some(entry) = location
+        .as_ref()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .map(|content| PathBuf::from(content.trim()))
+        .filter(|entry| entry.is_file())
+    {
+        return Ok(entry);
+    }
+
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let detector = crate::runtime::RuntimeDetector::new();
+    let node = detector.node_path();
+    let entry = module_dirs(var, dirs::home_dir().as_deref(), node)
+        .iter()
+        .find_map(|dir| package_entry(&dir.join("openclaw")))
+        .context(
+            "Cannot find openclaw installation. Run 'openclaw' without arguments to install.",
+        )?;
+
+    if let Some(location) = location {
+        let _ = fs::write(location, entry.to_string_lossy().as_bytes());
+    }
+    Ok(entry)
+}
+
+/// Forget the remembered openclaw location, so the next lookup searches again
+pub fn forget_location() {
+    if let Ok(dir) = crate::cache::cache_dir() {
+        let _ = fs::remove_file(dir.join(LOCATION_FILE));
+    }
+}
+
+/// Global node_modules directories that may hold openclaw, most specific
+/// first: pnpm's global dirs, npm's configured prefix, the prefix of the node
+/// on PATH, Homebrew, and common system locations
+fn module_dirs(
+    var: impl Fn(&str) -> Option<String>,
+    home: Option<&Path>,
+    node: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    // pnpm: `global-dir` setting, $PNPM_HOME/global or the platform default,
+    // each holding one directory per global layout version (5, 6, ...)
+    let npmrc = home.map(|h| h.join(".npmrc"));
+    let pnpm_rc = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.map(|h| h.join(".config")))
+        .map(|d| d.join("pnpm").join("rc"));
+    let mut pnpm_globals: Vec<PathBuf> = [&pnpm_rc, &npmrc]
+        .into_iter()
+        .flatten()
+        .filter_map(|rc| rc_value(rc, "global-dir", &var, home))
+        .collect();
+    pnpm_globals.extend(var("PNPM_HOME").map(|d| PathBuf::from(d).join("global")));
+    pnpm_globals.extend(
+        var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.map(|h| h.join(".local/share")))
+            .map(|d| d.join("pnpm/global")),
+    );
+    pnpm_globals.extend(home.map(|h| h.join("Library/pnpm/global")));
+    pnpm_globals.extend(var("LOCALAPPDATA").map(|d| PathBuf::from(d).join("pnpm/global")));
+    for global in &pnpm_globals {
+        for layout in layout_versions(global) {
+            dirs.push(layout.join("node_modules"));
+            dirs.extend(pnpm_store_packages(&layout.join(".pnpm")));
+            dirs.extend(pnpm_store_packages(&layout.join("node_modules/.pnpm")));
+        }
+    }
+
+    // npm: configured prefix, else the prefix node was installed under
+    let node_prefix = node
+        .and_then(|n| fs::canonicalize(n).ok())
+        .and_then(|n| Some(n.parent()?.parent()?.to_path_buf()));
+    let user_npmrc = var("NPM_CONFIG_USERCONFIG").map(PathBuf::from).or(npmrc);
+    let global_npmrc = node_prefix.as_ref().map(|p| p.join("etc/npmrc"));
+    let mut prefixes: Vec<PathBuf> = var("NPM_CONFIG_PREFIX")
+        .or_else(|| var("npm_config_prefix"))
+        .map(|p| expand(&p, &var, home))
+        .into_iter()
+        .collect();
+    prefixes.extend(
+        [&user_npmrc, &global_npmrc]
+            .into_iter()
+            .flatten()
+            .filter_map(|rc| rc_value(rc, "prefix", &var, home)),
+    );
+    prefixes.extend(node_prefix);
+
+    // Homebrew, and installs predating any of the above
+    prefixes.extend(var("HOMEBREW_PREFIX").map(PathBuf::from));
+    prefixes.push(PathBuf::from("/opt/homebrew"));
+    prefixes.push(PathBuf::from("/usr/local"));
+    prefixes.push(PathBuf::from("/usr"));
+    prefixes.extend(home.map(|h| h.join(".npm-global")));
+    for prefix in prefixes {
+        if cfg!(windows) {
+            dirs.push(prefix.join("node_modules"));
+        } else {
+            dirs.push(prefix.join("lib/node_modules"));
+        }
+    }
+    dirs.extend(home.map(|h| h.join("node_modules")));
+
+    let mut seen = std::collections::HashSet::new();
+    dirs.retain(|dir| seen.insert(dir.clone()));
+    dirs
+}
+
+/// Numbered layout directories in a pnpm global dir, newest first
+fn layout_versions(global: &Path) -> Vec<PathBuf> {
+    let mut versions: Vec<(u32, PathBuf)> = fs::read_dir(global)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| Some((entry.file_name().to_str()?.parse().ok()?, entry.path())))
+        .collect();
+    versions.sort_by_key(|(version, _)| std::cmp::Reverse(*version));
+    versions.into_iter().map(|(_, path)| path).collect()
+}
+
+/// node_modules directories of `openclaw@<version>` packages in a pnpm store
+fn pnpm_store_packages(store: &Path) -> Vec<PathBuf> {
+    fs::read_dir(store)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("openclaw@"))
+        .map(|entry| entry.path().join("node_modules"))
+        .collect()
+}
+
+/// Read a `key=value` setting from an npmrc-style file
+fn rc_value(
+    rc: &Path,
+    key: &str,
+    var: impl Fn(&str) -> Option<String>,
+    home: Option<&Path>,
+) -> Option<PathBuf> {
+    let content = fs::read_to_string(rc).ok()?;
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        if line.starts_with(';') || line.starts_with('#') {
+            return None;
+        }
+        let (name, value) = line.split_once('=')?;
+        if name.trim() != key {
+            return None;
+        }
+        let value = value.trim().trim_matches('"');
+        (!value.is_empty()).then(|| expand(value, &var, home))
+    })
+}
+
+/// Expand `${VAR}` references and a leading `~/` in an npmrc value
+fn expand(value: &str, var: impl Fn(&str) -> Option<String>, home: Option<&Path>) -> PathBuf {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&var(&rest[start + 2..start + end]).unwrap_or_default());
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+
+    match (expanded.strip_prefix("~/"), home) {
+        (Some(relative), Some(home)) => home.join(relative),
+        _ => PathBuf::from(expanded),
+    }
+}
+
+/// Get the installed openclaw version, falling back to the version chitin was
+/// built against if it cannot be detected. Detected once per process.
+pub fn version() -> &'static str {
+    installed_version().unwrap_or(crate::OPENCLAW_VERSION)
+}
+
+/// Get the installed openclaw version, detected once per process
+pub fn installed_version() -> Option<&'static str> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+
+    VERSION.get_or_init(detect_version).as_deref()
+}
+
+/// Detect the installed openclaw version from the package.json next to its
+/// entry point. Only reads a few small files, so it is cheap to call on every run.
+pub fn detect_version() -> Option<String> {
+    let entry = find_entry().ok()?;
+    package_version(entry.parent()?)
+}
+
+/// Find the openclaw.mjs entry point, preferring the one the shim on PATH runs
+pub fn find_entry() -> Result<PathBuf> {
+    match find_shim().and_then(|shim| entry_from_shim(&shim)) {
+        Some(entry) => Ok(entry),
+        None => find_openclaw_mjs(),
+    }
+}
+
+/// Resolve the openclaw.mjs a shim points at. npm installs the shim as a symlink
+/// to the entry point; pnpm writes a shell script that execs node on it.
+fn entry_from_shim(shim: &Path) -> Option<PathBuf> {
+    let resolved = fs::canonicalize(shim).ok()?;
+    if resolved.file_name().is_some_and(|n| n == "openclaw.mjs") {
+        return Some(resolved);
+    }
+
+    let script = fs::read_to_string(&resolved).ok()?;
+    let basedir = resolved.parent()?.to_string_lossy().to_string();
+
+    script
+        .split('"')
+        .filter(|s| s.ends_with("openclaw.mjs"))
+        .map(|s| PathBuf::from(s.replace("$basedir", &basedir)))
+        .find(|p| p.exists())
+}
+
+/// Get the Node.js version range the installed openclaw supports (the
+/// `engines.node` field of its package.json), read once per process
+pub fn node_requirement() -> Option<&'static str> {
+    static REQUIREMENT: OnceLock<Option<String>> = OnceLock::new();
+
+    REQUIREMENT
+        .get_or_init(|| {
+            let entry = find_entry().ok()?;
+            package_field(entry.parent()?, "/engines/node")
+        })
+        .as_deref()
+}
+
+/// Read the "version" field of the package.json in a package directory
+fn package_version(package_dir: &Path) -> Option<String> {
+    package_field(package_dir, "/version")
+}
+
+/// Read a string field of the package.json in a package directory
+fn package_field(package_dir: &Path, pointer: &str) -> Option<String> {
+    let content = fs::read_to_string(package_dir.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    package.pointer(pointer)?.as_str().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("chitin-openclaw-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_package_version() {
+        let dir = temp_dir("version");
+        assert_eq!(package_version(&dir), None);
+
+        fs::write(
+            dir.join("package.json"),
+            r#"{"name": "openclaw", "version": "2026.6.1", "engines": {"node": ">=22.12.0"}}"#,
+        )
+        .unwrap();
+        assert_eq!(package_version(&dir), Some("2026.6.1".to_string()));
+        assert_eq!(
+            package_field(&dir, "/engines/node"),
+            Some(">=22.12.0".to_string())
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_entry_at() {
+        let dir = temp_dir("entry");
+        assert_eq!(entry_at(&dir), None);
+
+        fs::write(dir.join("openclaw.mjs"), "").unwrap();
+        assert_eq!(entry_at(&dir), Some(dir.join("openclaw.mjs")));
+        assert_eq!(
+            entry_at(&dir.join("openclaw.mjs")),
+            Some(dir.join("openclaw.mjs"))
+        );
+        assert_eq!(entry_at(&dir.join("missing.mjs")), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_package_entry() {
+        let dir = temp_dir("bin");
+        fs::write(dir.join("openclaw.mjs"), "").unwrap();
+        fs::write(dir.join("cli.mjs"), "").unwrap();
+        assert_eq!(package_entry(&dir), Some(dir.join("openclaw.mjs")));
+
+        fs::write(dir.join("package.json"), r#"{"bin": "cli.mjs"}"#).unwrap();
+        assert_eq!(package_entry(&dir), Some(dir.join("cli.mjs")));
+
+        fs::write(
+            dir.join("package.json"),
+            r#"{"bin": {"oc": "openclaw.mjs", "openclaw": "./cli.mjs"}}"#,
+        )
+        .unwrap();
+        assert_eq!(package_entry(&dir), Some(dir.join("./cli.mjs")));
+
+        fs::write(dir.join("package.json"), r#"{"bin": "missing.mjs"}"#).unwrap();
+        assert_eq!(package_entry(&dir), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_module_dirs() {
+        let home = temp_dir("modules");
+        let pnpm_home = home.join("pnpm");
+        fs::create_dir_all(pnpm_home.join("global/5")).unwrap();
+        fs::create_dir_all(pnpm_home.join("global/10")).unwrap();
+        fs::write(
+            home.join(".npmrc"),
+            "; comment\nprefix=${HOME}/.npm-custom\n",
+        )
+        .unwrap();
+
+        let var = |name: &str| match name {
+            "HOME" => Some(home.to_string_lossy().to_string()),
+            "PNPM_HOME" => Some(pnpm_home.to_string_lossy().to_string()),
+            _ => None,
+        };
+        let dirs = module_dirs(var, Some(&home), None);
+
+        let position = |dir: PathBuf| dirs.iter().position(|d| *d == dir).unwrap();
+        assert!(
+            position(pnpm_home.join("global/10/node_modules"))
+                < position(pnpm_home.join("global/5/node_modules"))
+        );
+        let npm_modules = if cfg!(windows) {
+            home.join(".npm-custom/node_modules")
+        } else {
+            home.join(".npm-custom/lib/node_modules")
+        };
+        assert!(position(npm_modules.clone()) > position(pnpm_home.join("global/5/node_modules")));
+
+        // The first directory holding the package wins
+        let package = npm_modules.join("openclaw");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("openclaw.mjs"), "").unwrap();
+        let found = dirs
+            .iter()
+            .find_map(|dir| package_entry(&dir.join("openclaw")));
+        assert_eq!(found, Some(package.join("openclaw.mjs")));
+
+        let _ = fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn test_expand() {
+        let var = |name: &str| (name == "PREFIX").then(|| "/opt/node".to_string());
+        let home = Path::new("/home/me");
+        assert_eq!(
+            expand("${PREFIX}/global", var, Some(home)),
+            PathBuf::from("/opt/node/global")
+        );
+        assert_eq!(
+            expand("~/.npm-global", var, Some(home)),
+            PathBuf::from("/home/me/.npm-global")
+        );
+        assert_eq!(expand("${UNSET}/x", var, Some(home)), PathBuf::from("/x"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_same_file() {
+        let dir = temp_dir("same");
+        let binary = dir.join("chitin");
+        fs::write(&binary, "").unwrap();
+        fs::write(dir.join("other"), "").unwrap();
+        std::os::unix::fs::symlink(&binary, dir.join("openclaw")).unwrap();
+        fs::hard_link(&binary, dir.join("linked")).unwrap();
+
+        assert!(same_file(&dir.join("openclaw"), &binary));
+        assert!(same_file(&dir.join("linked"), &binary));
+        assert!(!same_file(&dir.join("other"), &binary));
+        assert!(!same_file(&dir.join("missing"), &binary));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_next_depth() {
+        assert_eq!(next_depth(None).unwrap(), 1);
+        assert_eq!(next_depth(Some("3")).unwrap(), 4);
+        assert_eq!(next_depth(Some("junk")).unwrap(), 1);
+        assert!(next_depth(Some("8")).is_err());
+    }
+
+    #[test]
+    fn test_entry_from_pnpm_shim() {
+        let dir = temp_dir("shim");
+        let package = dir.join("global/5/node_modules/openclaw");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("openclaw.mjs"), "").unwrap();
+
+        let shim = dir.join("openclaw");
+        fs::write(
+            &shim,
+            "#!/bin/sh\nbasedir=$(dirname \"$0\")\nexec node  \"$basedir/global/5/node_modules/openclaw/openclaw.mjs\" \"$@\"\n",
+        )
+        .unwrap();
+
+        let entry = entry_from_shim(&shim).unwrap();
+        assert_eq!(
+            fs::canonicalize(entry).unwrap(),
+            fs::canonicalize(package.join("openclaw.mjs")).unwrap()
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,645 @@
+//! Native `config get/set/unset/list` - reads and edits openclaw's JSON5 config
+//! file directly instead of starting Node.js. Edits keep the file's formatting
+//! and comments; keys outside the known schema are left to openclaw.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use crate::json5;
+use crate::native::{Flag, NativeCommand, Outcome};
+
+/// Port the gateway listens on when `gateway.port` is not set
+pub const DEFAULT_GATEWAY_PORT: u16 = 18789;
+
+/// Type of a known config value, used to validate and convert `config set` input
+enum ValueType {
+    String,
+    Integer { min: i64, max: i64 },
+    Bool,
+    Enum(&'static [&'static str]),
+    StringList,
+}
+
+const DM_POLICIES: &[&str] = &["pairing", "allowlist", "open", "disabled"];
+const GROUP_POLICIES: &[&str] = &["open", "allowlist", "disabled"];
+const LOG_LEVELS: &[&str] = &["silent", "fatal", "error", "warn", "info", "debug", "trace"];
+const THINKING_LEVELS: &[&str] = &["off", "minimal", "low", "medium", "high"];
+
+/// Keys that can be set natively; `*` matches any single key (e.g. a channel name)
+const SCHEMA: &[(&str, ValueType)] = &[
+    ("gateway.port", ValueType::Integer { min: 1, max: 65535 }),
+    ("gateway.mode", ValueType::Enum(&["local", "remote"])),
+    (
+        "gateway.bind",
+        ValueType::Enum(&["loopback", "lan", "tailnet", "auto", "custom"]),
+    ),
+    ("gateway.customBindHost", ValueType::String),
+    ("gateway.auth.mode", ValueType::Enum(&["token", "password"])),
+    ("gateway.auth.token", ValueType::String),
+    ("gateway.auth.password", ValueType::String),
+    ("gateway.remote.url", ValueType::String),
+    ("gateway.remote.token", ValueType::String),
+    ("agents.defaults.workspace", ValueType::String),
+    ("agents.defaults.model.primary", ValueType::String),
+    ("agents.defaults.model.fallbacks", ValueType::StringList),
+    (
+        "agents.defaults.thinkingDefault",
+        ValueType::Enum(THINKING_LEVELS),
+    ),
+    (
+        "agents.defaults.timeoutSeconds",
+        ValueType::Integer {
+            min: 1,
+            max: i64::MAX,
+        },
+    ),
+    (
+        "agents.defaults.maxConcurrent",
+        ValueType::Integer { min: 1, max: 1024 },
+    ),
+    ("channels.*.enabled", ValueType::Bool),
+    ("channels.*.dmPolicy", ValueType::Enum(DM_POLICIES)),
+    ("channels.*.groupPolicy", ValueType::Enum(GROUP_POLICIES)),
+    ("channels.*.allowFrom", ValueType::StringList),
+    ("logging.level", ValueType::Enum(LOG_LEVELS)),
+    ("logging.file", ValueType::String),
+    ("session.scope", ValueType::Enum(&["per-sender", "global"])),
+    ("messages.ackReaction", ValueType::String),
+    (
+        "update.channel",
+        ValueType::Enum(&["stable", "beta", "dev"]),
+    ),
+    ("browser.enabled", ValueType::Bool),
+];
+
+/// Find the schema entry for a dotted key path
+fn schema_type(path: &[&str]) -> Option<&'static ValueType> {
+    SCHEMA.iter().find_map(|(pattern, value_type)| {
+        let pattern: Vec<&str> = pattern.split('.').collect();
+        let matches = pattern.len() == path.len()
+            && pattern.iter().zip(path).all(|(p, k)| *p == "*" || p == k);
+        matches.then_some(value_type)
+    })
+}
+
+/// Get openclaw's state directory: `$OPENCLAW_STATE_DIR`, then `~/.openclaw`
+pub fn state_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("OPENCLAW_STATE_DIR").filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    let home = dirs::home_dir().context("Cannot find home directory")?;
+    Ok(home.join(".openclaw"))
+}
+
+/// Get the openclaw config file: `$OPENCLAW_CONFIG_PATH`, then `openclaw.json`
+/// in the state directory
+pub fn config_path() -> Result<PathBuf> {
+    if let Some(path) = std::env::var_os("OPENCLAW_CONFIG_PATH").filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(state_dir()?.join("openclaw.json"))
+}
+
+/// Read and parse the openclaw config, or None if there is no config file
+pub fn load() -> Result<Option<Value>> {
+    let path = config_path()?;
+    match read(&path)? {
+        Some(text) => Ok(Some(parse(&path, &text)?.to_value())),
+        None => Ok(None),
+    }
+}
+
+/// Port the local gateway listens on (`gateway.port`)
+pub fn gateway_port(config: Option<&Value>) -> u16 {
+    config
+        .and_then(|c| c.pointer("/gateway/port"))
+        .and_then(Value::as_u64)
+        .and_then(|port| u16::try_from(port).ok())
+        .unwrap_or(DEFAULT_GATEWAY_PORT)
+}
+
+/// Host the local gateway can be reached on, from `gateway.bind`: loopback
+/// for `loopback`, `lan` and `auto`, which all listen there, or
+/// `gateway.customBindHost` for `custom`. None for `tailnet`, whose address
+/// only Tailscale knows.
+pub fn gateway_host(config: Option<&Value>) -> Option<String> {
+    let get = |pointer: &str| {
+        config
+            .and_then(|c| c.pointer(pointer))
+            .and_then(Value::as_str)
+    };
+    let host = match get("/gateway/bind").unwrap_or("loopback") {
+        "loopback" | "lan" | "auto" => "127.0.0.1",
+        "custom" => get("/gateway/customBindHost")?,
+        _ => return None,
+    };
+    // Listening on every interface includes loopback
+    Some(match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) if ip.is_unspecified() => "127.0.0.1".to_string(),
+        Ok(IpAddr::V6(ip)) if ip.is_unspecified() => "::1".to_string(),
+        _ => host.to_string(),
+    })
+}
+
+fn read(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn parse(path: &Path, text: &str) -> Result<json5::Node> {
+    json5::parse(text).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Whether the config pulls in other files, which only openclaw resolves
+fn has_includes(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.contains_key("$include") || map.values().any(has_includes),
+        Value::Array(items) => items.iter().any(has_includes),
+        _ => false,
+    }
+}
+
+/// One step of a config path: `agents.list[0].id` is Key, Key, Index, Key
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn parse_path(path: &str) -> Option<Vec<Segment>> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (key, mut indexes) = match part.find('[') {
+            Some(i) => part.split_at(i),
+            None => (part, ""),
+        };
+        if key.is_empty() {
+            return None;
+        }
+        segments.push(Segment::Key(key.to_string()));
+        while let Some(rest) = indexes.strip_prefix('[') {
+            let end = rest.find(']')?;
+            segments.push(Segment::Index(rest[..end].parse().ok()?));
+            indexes = &rest[end + 1..];
+        }
+        if !indexes.is_empty() {
+            return None;
+        }
+    }
+    Some(segments)
+}
+
+fn lookup<'a>(value: &'a Value, path: &[Segment]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match segment {
+        Segment::Key(key) => value.get(key),
+        Segment::Index(index) => value.get(index),
+    })
+}
+
+/// Object-key-only paths, the only kind that can be edited natively
+fn key_path(path: &[Segment]) -> Option<Vec<&str>> {
+    path.iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => Some(key.as_str()),
+            Segment::Index(_) => None,
+        })
+        .collect()
+}
+
+/// Convert `config set` input to a value of the expected type
+fn convert(path: &str, value_type: &ValueType, input: &str, json: bool) -> Result<Value> {
+    let invalid =
+        |expected: String| anyhow::anyhow!("Invalid value for {}: expected {}", path, expected);
+
+    // --json takes the value literally, checked against the schema type
+    let parsed = if json {
+        Some(
+            json5::parse(input)
+                .map(|node| node.to_value())
+                .map_err(|e| anyhow::anyhow!("Invalid JSON value for {}: {}", path, e))?,
+        )
+    } else {
+        None
+    };
+
+    match value_type {
+        ValueType::String => match parsed {
+            Some(Value::String(s)) => Ok(Value::String(s)),
+            Some(_) => Err(invalid("a string".to_string())),
+            None => Ok(Value::String(input.to_string())),
+        },
+        ValueType::Integer { min, max } => {
+            let n = match parsed {
+                Some(value) => value.as_i64(),
+                None => input.trim().parse().ok(),
+            };
+            let range = if *max == i64::MAX {
+                format!("an integer of at least {}", min)
+            } else {
+                format!("an integer between {} and {}", min, max)
+            };
+            match n {
+                Some(n) if (*min..=*max).contains(&n) => Ok(Value::from(n)),
+                _ => Err(invalid(range)),
+            }
+        }
+        ValueType::Bool => {
+            let b = match parsed {
+                Some(value) => value.as_bool(),
+                None => match input.trim().to_lowercase().as_str() {
+                    "true" => Some(true),
+                    "false" => Some(false),
+                    _ => None,
+                },
+            };
+            b.map(Value::Bool)
+                .ok_or_else(|| invalid("true or false".to_string()))
+        }
+        ValueType::Enum(options) => {
+            let s = match parsed {
+                Some(Value::String(s)) => s,
+                Some(_) => String::new(),
+                None => input.trim().to_string(),
+            };
+            if options.contains(&s.as_str()) {
+                Ok(Value::String(s))
+            } else {
+                Err(invalid(format!("one of {}", options.join(", "))))
+            }
+        }
+        ValueType::StringList => {
+            let list = match parsed {
+                Some(value) => value,
+                None if input.trim_start().starts_with('[') => json5::parse(input)
+                    .map(|node| node.to_value())
+                    .map_err(|_| invalid("a list of strings".to_string()))?,
+                None => Value::from(
+                    input
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>(),
+                ),
+            };
+            match list.as_array() {
+                Some(items) if items.iter().all(Value::is_string) => Ok(list),
+                _ => Err(invalid("a list of strings".to_string())),
+            }
+        }
+    }
+}
+
+/// Flatten a parsed config into (path, leaf value) pairs in file order
+fn flatten(prefix: &str, node: &json5::Node, out: &mut Vec<(String, Value)>) {
+    match &node.kind {
+        json5::Kind::Object(members) if !members.is_empty() => {
+            for member in members {
+                let path = if prefix.is_empty() {
+                    member.key.clone()
+                } else {
+                    format!("{}.{}", prefix, member.key)
+                };
+                flatten(&path, &member.value, out);
+            }
+        }
+        _ => out.push((prefix.to_string(), node.to_value())),
+    }
+}
+
+/// Print a value: strings as-is, everything else as JSON
+fn print_value(value: &Value, json: bool) -> Result<()> {
+    match value {
+        Value::String(s) if !json => println!("{}", s),
+        _ => println!("{}", serde_json::to_string_pretty(value)?),
+    }
+    Ok(())
+}
+
+/// Write the config atomically, keeping the existing file's permissions.
+/// New files are only readable by the owner since they may hold credentials.
+/// Replace the config file atomically, keeping its mode and owner. A symlinked
+/// config (e.g. from a dotfiles repo) stays a link; the file it points to is
+/// replaced instead.
+fn write(path: &Path, text: &str) -> Result<()> {
+    let path = &resolve_link(path);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create openclaw config directory")?;
+    }
+
+    let tmp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    let result = fs::write(&tmp_path, text)
+        .and_then(|_| match fs::metadata(path) {
+            Ok(metadata) => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::MetadataExt;
+                    // Only root can give the file away; others already own it
+                    let _ = std::os::unix::fs::chown(
+                        &tmp_path,
+                        Some(metadata.uid()),
+                        Some(metadata.gid()),
+                    );
+                }
+                fs::set_permissions(&tmp_path, metadata.permissions())
+            }
+            Err(_) => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+                }
+                Ok(())
+            }
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The file `path` refers to after following symlinks, including a link whose
+/// target doesn't exist yet
+fn resolve_link(path: &Path) -> PathBuf {
+    if let Ok(resolved) = fs::canonicalize(path) {
+        return resolved;
+    }
+    match fs::read_link(path) {
+        Ok(target) => path.parent().unwrap_or(Path::new("")).join(target),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Flags the native handler supports
+const FLAGS: &[Flag] = &[Flag::switch("--json")];
+
+/// `chitin config <get|set|unset|list>`. Other subcommands, unknown keys,
+/// array paths and configs with `$include` are delegated.
+pub struct ConfigCommand;
+
+impl NativeCommand for ConfigCommand {
+    fn path(&self) -> &'static [&'static str] {
+        &["config"]
+    }
+
+    fn flags(&self) -> &'static [Flag] {
+        FLAGS
+    }
+
+    fn run(&self, args: &[String]) -> Result<Outcome> {
+        let json = args.iter().any(|a| a == "--json");
+        let positional: Vec<&str> = args
+            .iter()
+            .filter(|a| *a != "--json")
+            .map(|s| s.as_str())
+            .collect();
+        run(&positional, json)
+    }
+}
+
+/// A `config` subcommand chitin handles natively
+enum Request<'a> {
+    Get(&'a str, Vec<Segment>),
+    List,
+    Set(&'a str, Vec<String>, &'static ValueType, &'a str),
+    Unset(&'a str, Vec<String>),
+}
+
+/// Recognize the subcommand before touching the config file, so anything
+/// chitin doesn't handle goes to openclaw untouched
+fn request<'a>(positional: &[&'a str]) -> Option<Request<'a>> {
+    match *positional {
+        ["get", key] => Some(Request::Get(key, parse_path(key)?)),
+        ["list"] => Some(Request::List),
+        ["set", key, input] => {
+            let segments = parse_path(key)?;
+            let keys = key_path(&segments)?;
+            let value_type = schema_type(&keys)?;
+            Some(Request::Set(key, owned(&keys), value_type, input))
+        }
+        ["unset", key] => {
+            let segments = parse_path(key)?;
+            let keys = key_path(&segments)?;
+            schema_type(&keys)?;
+            Some(Request::Unset(key, owned(&keys)))
+        }
+        _ => None,
+    }
+}
+
+fn owned(keys: &[&str]) -> Vec<String> {
+    keys.iter().map(|key| key.to_string()).collect()
+}
+
+fn run(positional: &[&str], json: bool) -> Result<Outcome> {
+    let Some(request) = request(positional) else {
+        return Ok(Outcome::Delegate);
+    };
+
+    // openclaw reports unreadable or invalid configs in its own words
+    let path = config_path()?;
+    let Ok(text) = read(&path) else {
+        return Ok(Outcome::Delegate);
+    };
+    let root = match &text {
+        Some(text) => parse(&path, text),
+        None => json5::parse("{}"),
+    };
+    let Ok(root) = root else {
+        return Ok(Outcome::Delegate);
+    };
+    let config = root.to_value();
+    if has_includes(&config) {
+        return Ok(Outcome::Delegate);
+    }
+
+    match request {
+        Request::Get(key, segments) => match lookup(&config, &segments) {
+            Some(value) => print_value(value, json)?,
+            None => anyhow::bail!("Config path not found: {}", key),
+        },
+        Request::List => {
+            if json {
+                println!("{}", serde_json::to_string_pretty(&config)?);
+            } else {
+                let mut entries = Vec::new();
+                flatten("", &root, &mut entries);
+                for (key, value) in entries.iter().filter(|(k, _)| !k.is_empty()) {
+                    println!("{} = {}", key, value);
+                }
+            }
+        }
+        Request::Set(key, keys, value_type, input) => {
+            let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+            let value = convert(key, value_type, input, json)?;
+            let text = text.unwrap_or_else(|| "{}\n".to_string());
+            let edited =
+                json5::set(&text, &keys, &value).with_context(|| format!("Cannot set {}", key))?;
+            write(&path, &edited)?;
+            println!("Updated {}", key);
+        }
+        Request::Unset(key, keys) => {
+            let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+            match text
+                .map(|text| json5::unset(&text, &keys))
+                .transpose()?
+                .flatten()
+            {
+                Some(edited) => {
+                    write(&path, &edited)?;
+                    println!("Removed {}", key);
+                }
+                None => println!("{} is not set", key),
+            }
+        }
+    }
+
+    Ok(Outcome::Handled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_lookup() {
+        assert!(matches!(
+            schema_type(&["gateway", "port"]),
+            Some(ValueType::Integer { .. })
+        ));
+        assert!(matches!(
+            schema_type(&["channels", "telegram", "enabled"]),
+            Some(ValueType::Bool)
+        ));
+        assert!(schema_type(&["gateway"]).is_none());
+        assert!(schema_type(&["plugins", "entries"]).is_none());
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("agents.list[0].id").unwrap(),
+            vec![
+                Segment::Key("agents".into()),
+                Segment::Key("list".into()),
+                Segment::Index(0),
+                Segment::Key("id".into()),
+            ]
+        );
+        assert!(parse_path("gateway..port").is_none());
+        assert!(parse_path("list[x]").is_none());
+
+        let config = json!({"agents": {"list": [{"id": "main"}]}});
+        let segments = parse_path("agents.list[0].id").unwrap();
+        assert_eq!(lookup(&config, &segments), Some(&json!("main")));
+        assert_eq!(key_path(&segments), None);
+    }
+
+    #[test]
+    fn test_request() {
+        assert!(request(&[]).is_none());
+        assert!(request(&["file"]).is_none());
+        assert!(request(&["get", "agents..id"]).is_none());
+        assert!(request(&["set", "plugins.entries", "x"]).is_none());
+        assert!(request(&["unset", "agents.list[0].id"]).is_none());
+        assert!(matches!(
+            request(&["get", "agents.list[0].id"]),
+            Some(Request::Get(..))
+        ));
+        assert!(matches!(request(&["list"]), Some(Request::List)));
+        assert!(matches!(
+            request(&["set", "gateway.port", "19000"]),
+            Some(Request::Set(_, _, ValueType::Integer { .. }, "19000"))
+        ));
+        assert!(matches!(
+            request(&["unset", "browser.enabled"]),
+            Some(Request::Unset(..))
+        ));
+    }
+
+    #[test]
+    fn test_convert() {
+        let port = schema_type(&["gateway", "port"]).unwrap();
+        assert_eq!(
+            convert("gateway.port", port, "19001", false).unwrap(),
+            json!(19001)
+        );
+        assert!(convert("gateway.port", port, "0", false).is_err());
+        assert!(convert("gateway.port", port, "http", false).is_err());
+
+        let token = schema_type(&["gateway", "auth", "token"]).unwrap();
+        assert_eq!(convert("t", token, "123", false).unwrap(), json!("123"));
+        assert_eq!(convert("t", token, "\"abc\"", true).unwrap(), json!("abc"));
+        assert!(convert("t", token, "123", true).is_err());
+
+        let mode = schema_type(&["gateway", "mode"]).unwrap();
+        assert!(convert("gateway.mode", mode, "remote", false).is_ok());
+        let error = convert("gateway.mode", mode, "cloud", false).unwrap_err();
+        assert!(error.to_string().contains("one of local, remote"));
+
+        let allow = schema_type(&["channels", "whatsapp", "allowFrom"]).unwrap();
+        assert_eq!(
+            convert("a", allow, "+1555, +1666", false).unwrap(),
+            json!(["+1555", "+1666"])
+        );
+        assert_eq!(
+            convert("a", allow, "['+1555']", false).unwrap(),
+            json!(["+1555"])
+        );
+        assert!(convert("a", allow, "[1]", false).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_through_symlink() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("chitin-config-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("dotfiles")).unwrap();
+        let target = dir.join("dotfiles/openclaw.json");
+        fs::write(&target, "{}").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o640)).unwrap();
+        let link = dir.join("openclaw.json");
+        std::os::unix::fs::symlink("dotfiles/openclaw.json", &link).unwrap();
+
+        write(&link, "{ gateway: { port: 19000 } }").unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "{ gateway: { port: 19000 } }"
+        );
+        let mode = fs::metadata(&target).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_flatten_and_includes() {
+        let root =
+            json5::parse("{gateway: {port: 18789, auth: {token: 't'}}, tags: ['a']}").unwrap();
+        let mut entries = Vec::new();
+        flatten("", &root, &mut entries);
+        assert_eq!(
+            entries,
+            vec![
+                ("gateway.port".to_string(), json!(18789)),
+                ("gateway.auth.token".to_string(), json!("t")),
+                ("tags".to_string(), json!(["a"])),
+            ]
+        );
+
+        assert!(!has_includes(&root.to_value()));
+        assert!(has_includes(
+            &json!({"agents": {"$include": "./agents.json5"}})
+        ));
+    }
+}
@@ -0,0 +1,198 @@
+//! Prefetch module - walks the full command tree and caches every command's help
+//! using a bounded pool of concurrent Node.js processes.
+
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+
+use crate::cache::HelpCache;
+use crate::command_tree;
+
+/// Default number of concurrent `openclaw --help` processes
+pub const DEFAULT_JOBS: usize = 4;
+
+/// Deepest command path to descend into
+const MAX_DEPTH: usize = 6;
+
+/// Outcome of a prefetch run
+pub struct PrefetchReport {
+    /// Number of commands whose help was cached
+    pub cached: usize,
+    /// Command paths that could not be fetched, with the error
+    pub failed: Vec<(String, String)>,
+}
+
+/// Work shared between the prefetch workers
+#[derive(Default)]
+struct State {
+    /// Command paths waiting to be fetched
+    queue: VecDeque<Vec<String>>,
+    /// Number of paths currently being fetched
+    in_flight: usize,
+    /// Fetched (path, help) pairs
+    fetched: Vec<(String, String)>,
+    failed: Vec<(String, String)>,
+}
+
+/// Fetch help for the main command and every subcommand discovered from it,
+/// then write all results to the cache in one update
+pub fn prefetch_all(
+    cache: &HelpCache,
+    openclaw_version: &str,
+    jobs: usize,
+) -> Result<PrefetchReport> {
+    fetch_and_save(cache, openclaw_version, vec![Vec::new()], true, jobs)
+}
+
+/// Re-fetch help for the given command paths (e.g. "channels login") and write
+/// the results to the cache in one update
+pub fn refresh_paths(
+    cache: &HelpCache,
+    openclaw_version: &str,
+    paths: &[String],
+    jobs: usize,
+) -> Result<PrefetchReport> {
+    let seeds = paths
+        .iter()
+        .map(|p| p.split_whitespace().map(String::from).collect())
+        .collect();
+    fetch_and_save(cache, openclaw_version, seeds, false, jobs)
+}
+
+fn fetch_and_save(
+    cache: &HelpCache,
+    openclaw_version: &str,
+    seeds: Vec<Vec<String>>,
+    discover: bool,
+    jobs: usize,
+) -> Result<PrefetchReport> {
+    let fetched = walk(seeds, discover, jobs, crate::run_node_help);
+
+    // A full walk replaces the cache, dropping commands openclaw no longer has,
+    // but only once the main help was fetched; otherwise what was fetched is
+    // merged and the rest of the cache kept
+    let complete = discover && fetched.fetched.iter().any(|(path, _)| path.is_empty());
+    if complete {
+        cache.replace_all_help(&fetched.fetched, openclaw_version, crate::CHITIN_VERSION)?;
+    } else if !fetched.fetched.is_empty() {
+        cache.save_all_help(&fetched.fetched, openclaw_version, crate::CHITIN_VERSION)?;
+    }
+
+    Ok(PrefetchReport {
+        cached: fetched.fetched.len(),
+        failed: fetched.failed,
+    })
+}
+
+/// Fetch help for the `seeds` command paths with up to `jobs` concurrent calls
+/// to `fetch`. With `discover`, walks the command tree breadth-first, queueing
+/// the subcommands listed in each fetched help text.
+fn walk<F>(seeds: Vec<Vec<String>>, discover: bool, jobs: usize, fetch: F) -> State
+where
+    F: Fn(&[String]) -> Result<String> + Sync,
+{
+    let state = Mutex::new(State {
+        queue: VecDeque::from(seeds),
+        ..Default::default()
+    });
+    let changed = Condvar::new();
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| {
+                loop {
+                    let path = {
+                        let mut state = state.lock().unwrap();
+                        loop {
+                            if let Some(path) = state.queue.pop_front() {
+                                state.in_flight += 1;
+                                break Some(path);
+                            }
+                            if state.in_flight == 0 {
+                                break None;
+                            }
+                            state = changed.wait(state).unwrap();
+                        }
+                    };
+                    let Some(path) = path else {
+                        changed.notify_all();
+                        return;
+                    };
+
+                    let result = fetch(&path);
+
+                    let mut state = state.lock().unwrap();
+                    state.in_flight -= 1;
+                    match result {
+                        Ok(help) => {
+                            if discover && path.len() < MAX_DEPTH {
+                                let node = command_tree::parse_help(&help);
+                                for subcommand in node.subcommands {
+                                    // Commander's implicit "help [command]" has nothing to fetch
+                                    if subcommand.name == "help" {
+                                        continue;
+                                    }
+                                    let mut child = path.clone();
+                                    child.push(subcommand.name);
+                                    state.queue.push_back(child);
+                                }
+                            }
+                            state.fetched.push((path.join(" "), help));
+                        }
+                        Err(e) => state.failed.push((path.join(" "), e.to_string())),
+                    }
+                    changed.notify_all();
+                }
+            });
+        }
+    });
+
+    state.into_inner().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn help_for(path: &[String]) -> Result<String> {
+        let commands = match path.join(" ").as_str() {
+            "" => "  channels  Manage channels\n  gateway   Run gateway\n  help [command]  help",
+            "channels" => "  login   Log in\n  logout  Log out",
+            "channels logout" => anyhow::bail!("logout help failed"),
+            _ => "",
+        };
+        Ok(format!(
+            "Usage: openclaw {} [options]\n\nCommands:\n{}\n",
+            path.join(" "),
+            commands
+        ))
+    }
+
+    #[test]
+    fn test_walk_discovers_nested_commands() {
+        let state = walk(vec![Vec::new()], true, 3, help_for);
+
+        let mut fetched: Vec<&str> = state.fetched.iter().map(|(p, _)| p.as_str()).collect();
+        fetched.sort();
+        assert_eq!(fetched, vec!["", "channels", "channels login", "gateway"]);
+
+        assert_eq!(state.failed.len(), 1);
+        assert_eq!(state.failed[0].0, "channels logout");
+    }
+
+    #[test]
+    fn test_walk_single_worker() {
+        let state = walk(vec![Vec::new()], true, 1, help_for);
+        assert_eq!(state.fetched.len(), 4);
+    }
+
+    #[test]
+    fn test_walk_without_discovery() {
+        let seeds = vec![vec!["channels".to_string()], vec!["gateway".to_string()]];
+        let state = walk(seeds, false, 2, help_for);
+
+        let mut fetched: Vec<&str> = state.fetched.iter().map(|(p, _)| p.as_str()).collect();
+        fetched.sort();
+        assert_eq!(fetched, vec!["channels", "gateway"]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Background refresh module - re-fetches expired help entries in a detached
+//! process, so stale help can be served immediately.
+
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::cache::HelpCache;
+
+/// Minimum time between two background refreshes (seconds)
+const REFRESH_INTERVAL: u64 = 60;
+
+/// Lock file shared by every chitin process, next to the cache file
+fn lock_path(cache: &HelpCache) -> PathBuf {
+    cache.path().with_file_name("refresh.lock")
+}
+
+fn open_lock(cache: &HelpCache) -> Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(lock_path(cache))
+        .context("Failed to open refresh lock")
+}
+
+/// Start a detached `chitin __refresh` unless a refresh is running or was
+/// started recently. The lock file holds the start time of the last refresh;
+/// checking and updating it under the lock keeps concurrent callers from
+/// spawning more than one.
+pub fn spawn_background(cache: &HelpCache) -> Result<()> {
+    let mut lock = open_lock(cache)?;
+    if lock.try_lock().is_err() {
+        // A refresh is in progress
+        return Ok(());
+    }
+
+    let mut content = String::new();
+    lock.read_to_string(&mut content)?;
+    let now = crate::cache::now();
+    let last_started: u64 = content.trim().parse().unwrap_or(0);
+    if now.saturating_sub(last_started) < REFRESH_INTERVAL {
+        return Ok(());
+    }
+
+    lock.set_len(0)?;
+    lock.seek(SeekFrom::Start(0))?;
+    write!(lock, "{}", now)?;
+    drop(lock);
+
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .arg("__refresh")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Detach from the terminal's process group so Ctrl-C doesn't interrupt it
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    command
+        .spawn()
+        .context("Failed to start background refresh")?;
+
+    Ok(())
+}
+
+/// Re-fetch every stale entry (`chitin __refresh`), holding the lock throughout
+pub fn run() -> Result<()> {
+    let cache = HelpCache::new()?;
+    let lock = open_lock(&cache)?;
+    if lock.try_lock().is_err() {
+        return Ok(());
+    }
+
+    let openclaw_version = crate::openclaw::version();
+    let paths = cache.stale_paths(openclaw_version, crate::CHITIN_VERSION)?;
+    if !paths.is_empty() {
+        crate::prefetch::refresh_paths(
+            &cache,
+            openclaw_version,
+            &paths,
+            crate::prefetch::DEFAULT_JOBS,
+        )?;
+    }
+
+    Ok(())
+}
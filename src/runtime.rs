@@ -1,17 +1,495 @@
-//! Runtime detection module for checking Node.js and package manager availability.
+//! Runtime detection module for checking JavaScript runtime (Node.js, Bun) and
+//! package manager availability.
 
-/// Runtime detector for Node.js and package managers
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+
+use crate::installer::PackageManager;
+use crate::node_managers::{self, DetectedNode, NodeManager};
+
+/// File in the cache directory remembering the version of the last node probed
+const NODE_VERSION_FILE: &str = "node_version.json";
+
+/// Same for bun
+const BUN_VERSION_FILE: &str = "bun_version.json";
+
+/// File in the cache directory recording whether bun ran the installed openclaw
+const BUN_CHECK_FILE: &str = "bun_check.json";
+
+/// Oldest Bun release chitin runs openclaw with
+const MIN_BUN_VERSION: Version = Version {
+    major: 1,
+    minor: 1,
+    patch: 0,
+};
+
+/// Runtime detector for Node.js, Bun and package managers
 pub struct RuntimeDetector {
-    node_available: bool,
+    /// `node` on PATH, or found through a version manager
+    node: Option<DetectedNode>,
+    /// `bun` on PATH or in its default install location
+    bun_path: Option<PathBuf>,
     npm_available: bool,
     pnpm_available: bool,
 }
 
+/// JavaScript runtimes that can run openclaw.mjs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsRuntime {
+    Node,
+    Bun,
+}
+
+impl JsRuntime {
+    /// Parse a runtime name ("node" or "bun")
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "node" | "nodejs" => Ok(JsRuntime::Node),
+            "bun" => Ok(JsRuntime::Bun),
+            other => anyhow::bail!("unknown runtime '{}' (expected node or bun)", other),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            JsRuntime::Node => "node",
+            JsRuntime::Bun => "bun",
+        }
+    }
+}
+
+/// A runtime release version
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// Parse `--version` output such as `v22.12.0` (node) or `1.1.38` (bun)
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('v');
+        let mut parts = text.split('.').map(|p| p.parse().ok());
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+        };
+        parts.next().is_none().then_some(version)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Installed Node.js that doesn't satisfy openclaw's `engines.node` range
+pub struct NodeMismatch {
+    /// The node binary that was checked
+    pub path: PathBuf,
+    /// Version manager that supplied `path`, if any
+    pub manager: Option<NodeManager>,
+    pub version: Version,
+    pub required: String,
+}
+
+impl NodeMismatch {
+    /// The closest newer major release that satisfies the requirement
+    pub fn upgrade_major(&self) -> Option<u64> {
+        (self.version.major + 1..=self.version.major + 16).find(|&major| {
+            let latest = Version {
+                major,
+                minor: u64::MAX,
+                patch: u64::MAX,
+            };
+            satisfies(latest, &self.required) == Some(true)
+        })
+    }
+}
+
+impl fmt::Display for NodeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "openclaw requires Node.js {}, but {}",
+            self.required,
+            self.path.display()
+        )?;
+        if let Some(manager) = self.manager {
+            write!(f, " (from {})", manager.name())?;
+        }
+        write!(f, " is {}", self.version)
+    }
+}
+
+/// Version recorded for one runtime binary
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct VersionProbe {
+    path: PathBuf,
+    /// Modification time (nanoseconds since the epoch) and size of the binary,
+    /// so a replaced binary is probed again
+    modified: u64,
+    size: u64,
+    version: String,
+}
+
+impl VersionProbe {
+    fn key(binary: &Path) -> Option<(PathBuf, u64, u64)> {
+        let path = fs::canonicalize(binary).ok()?;
+        let metadata = fs::metadata(&path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some((path, modified.as_nanos() as u64, metadata.len()))
+    }
+}
+
+/// Get the version of a runtime binary, from `cache_file` if it was probed
+/// before and the binary hasn't changed since, otherwise with `probe`
+fn cached_version(
+    cache_file: Option<&Path>,
+    binary: &Path,
+    probe: impl FnOnce(&Path) -> Option<Version>,
+) -> Option<Version> {
+    let Some((path, modified, size)) = VersionProbe::key(binary) else {
+        return probe(binary);
+    };
+
+    let cached = cache_file
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str::<VersionProbe>(&content).ok())
+        .filter(|p| p.path == path && p.modified == modified && p.size == size);
+    if let Some(version) = cached.and_then(|p| Version::parse(&p.version)) {
+        return Some(version);
+    }
+
+    let version = probe(&path)?;
+    if let Some(file) = cache_file {
+        let record = VersionProbe {
+            path,
+            modified,
+            size,
+            version: version.to_string(),
+        };
+        if let Ok(content) = serde_json::to_string(&record) {
+            let _ = fs::write(file, content);
+        }
+    }
+    Some(version)
+}
+
+/// Run `<binary> --version`
+fn probe_version(binary: &Path) -> Option<Version> {
+    let output = Command::new(binary).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Version::parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Check `version` against an npm semver range such as `>=22.12.0` or
+/// `^20.19.0 || >=22.12.0`. Returns None if the range can't be parsed.
+pub fn satisfies(version: Version, range: &str) -> Option<bool> {
+    let mut matched = false;
+    for set in range.split("||") {
+        let (low, below) = range_bounds(set)?;
+        matched |= version >= low && below.is_none_or(|below| version < below);
+    }
+    Some(matched)
+}
+
+/// Versions allowed by a space-separated comparator set, as `low <= v < below`
+fn range_bounds(set: &str) -> Option<(Version, Option<Version>)> {
+    // Rejoin operators written apart from their version (`>= 22`)
+    let mut tokens: Vec<String> = Vec::new();
+    for token in set.split_whitespace() {
+        match tokens.last_mut() {
+            Some(last) if last.chars().all(|c| "<>=^~".contains(c)) => last.push_str(token),
+            _ => tokens.push(token.to_string()),
+        }
+    }
+
+    let zero = Version {
+        major: 0,
+        minor: 0,
+        patch: 0,
+    };
+    let mut low = zero;
+    let mut below: Option<Version> = None;
+    let mut bound = |l: Version, b: Option<Version>| {
+        low = low.max(l);
+        below = match (below, b) {
+            (Some(x), Some(y)) => Some(x.min(y)),
+            (x, y) => x.or(y),
+        };
+    };
+
+    // Hyphen range: `22.12 - 24`
+    if let [from, dash, to] = tokens.as_slice()
+        && dash == "-"
+    {
+        let from = partial(from)?;
+        let to = partial(to)?;
+        bound(fill(&from), bump(&to));
+        return Some((low, below));
+    }
+
+    for token in &tokens {
+        let split = token
+            .find(|c: char| !"<>=^~".contains(c))
+            .unwrap_or(token.len());
+        let (op, version) = token.split_at(split);
+        let parts = partial(version)?;
+        if parts.is_empty() {
+            // `*` (or `>=*`) allows anything; `<*` is too odd to handle
+            if op.starts_with('<') {
+                return None;
+            }
+            continue;
+        }
+        let l = fill(&parts);
+        match op {
+            ">=" => bound(l, None),
+            ">" => bound(bump(&parts)?, None),
+            "<" => bound(zero, Some(l)),
+            "<=" => bound(zero, bump(&parts)),
+            "" | "=" => bound(l, bump(&parts)),
+            "~" => bound(l, bump(&parts[..parts.len().min(2)])),
+            "^" => {
+                // Everything up to the next change of the first non-zero part
+                let significant = parts
+                    .iter()
+                    .position(|&p| p != 0)
+                    .map_or(parts.len(), |i| i + 1);
+                bound(l, bump(&parts[..significant.min(parts.len())]))
+            }
+            _ => return None,
+        }
+    }
+    Some((low, below))
+}
+
+/// Numeric parts of a possibly partial version (`22`, `22.x`, `v22.12.0-rc.1`),
+/// up to the first wildcard
+fn partial(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim_start_matches('v');
+    let version = version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts = Vec::new();
+    for part in version.split('.').take(3) {
+        match part {
+            "" | "x" | "X" | "*" => break,
+            _ => parts.push(part.parse().ok()?),
+        }
+    }
+    Some(parts)
+}
+
+/// The lowest version matching partial `parts`
+fn fill(parts: &[u64]) -> Version {
+    let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+    Version {
+        major: part(0),
+        minor: part(1),
+        patch: part(2),
+    }
+}
+
+/// The lowest version above everything matching partial `parts`, or None when
+/// that is unbounded
+fn bump(parts: &[u64]) -> Option<Version> {
+    let (last, rest) = parts.split_last()?;
+    let mut bumped = rest.to_vec();
+    bumped.push(last.checked_add(1)?);
+    Some(fill(&bumped))
+}
+
+/// Find node once per process: on PATH, or where a version manager keeps it
+fn detected_node() -> Option<&'static DetectedNode> {
+    static NODE: OnceLock<Option<DetectedNode>> = OnceLock::new();
+
+    NODE.get_or_init(|| {
+        node_managers::detect(
+            |name| std::env::var(name).ok(),
+            dirs::home_dir().as_deref(),
+            std::env::current_dir().ok().as_deref(),
+            which::which("node").ok(),
+        )
+    })
+    .as_ref()
+}
+
+/// Let `command` find the detected node when it isn't on PATH (e.g. the
+/// `#!/usr/bin/env node` line of the openclaw shim), by putting its directory
+/// first on the child's PATH
+pub fn expose_node(command: &mut Command) {
+    let Some(node) = detected_node().filter(|node| !node.on_path) else {
+        return;
+    };
+    let Some(dir) = node.path.parent() else {
+        return;
+    };
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let dirs = std::iter::once(dir.to_path_buf()).chain(std::env::split_paths(&path));
+    if let Ok(joined) = std::env::join_paths(dirs) {
+        command.env("PATH", joined);
+    }
+}
+
+/// A command running the detected node (plain `node` if there is none)
+pub fn node_command() -> Command {
+    let mut command = match detected_node() {
+        Some(node) => Command::new(&node.path),
+        None => Command::new("node"),
+    };
+    expose_node(&mut command);
+    command
+}
+
+/// A command running `runtime`
+pub fn command(runtime: JsRuntime) -> Command {
+    match (runtime, find_bun()) {
+        (JsRuntime::Bun, Some(bun)) => Command::new(bun),
+        (JsRuntime::Bun, None) => Command::new("bun"),
+        (JsRuntime::Node, _) => node_command(),
+    }
+}
+
+/// Find bun on PATH, or where its installer puts it
+fn find_bun() -> Option<PathBuf> {
+    let bin = if cfg!(windows) { "bun.exe" } else { "bun" };
+    which::which("bun").ok().or_else(|| {
+        let install = std::env::var_os("BUN_INSTALL")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".bun")))?;
+        Some(install.join("bin").join(bin)).filter(|bun| bun.is_file())
+    })
+}
+
+/// Get the version of a runtime binary, cached in the cache directory
+fn runtime_version(binary: &Path, file: &str) -> Option<Version> {
+    let cache_file = crate::cache::cache_dir().ok().map(|dir| dir.join(file));
+    cached_version(cache_file.as_deref(), binary, probe_version)
+}
+
+/// Outcome of running the installed openclaw with one bun release
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct BunCheck {
+    bun_version: String,
+    entry: PathBuf,
+    openclaw_version: String,
+    compatible: bool,
+}
+
+/// Whether `bun` can run the installed openclaw: a recent enough release
+/// that answers `openclaw --version`. Each bun/openclaw pair is only tried
+/// once; the outcome is remembered in the cache directory. The second value
+/// is true when the outcome was just recorded.
+fn bun_compatible(bun: &Path) -> (bool, bool) {
+    let Some(version) = runtime_version(bun, BUN_VERSION_FILE) else {
+        return (false, false);
+    };
+    let (Ok(entry), Some(openclaw_version)) = (
+        crate::openclaw::find_entry(),
+        crate::openclaw::installed_version(),
+    ) else {
+        return (false, false);
+    };
+
+    let check_file = crate::cache::cache_dir()
+        .ok()
+        .map(|dir| dir.join(BUN_CHECK_FILE));
+    let mut check = BunCheck {
+        bun_version: version.to_string(),
+        entry,
+        openclaw_version: openclaw_version.to_string(),
+        compatible: false,
+    };
+    let previous = check_file
+        .as_ref()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str::<BunCheck>(&content).ok());
+    if let Some(previous) = previous {
+        check.compatible = previous.compatible;
+        if previous == check {
+            return (check.compatible, false);
+        }
+    }
+
+    let mut command = Command::new(bun);
+    command.arg(&check.entry).arg("--version");
+    let _ = crate::openclaw::guard(&mut command);
+    check.compatible = version >= MIN_BUN_VERSION
+        && command.output().is_ok_and(|output| {
+            output.status.success()
+                && String::from_utf8_lossy(&output.stdout).contains(openclaw_version)
+        });
+
+    if let (Some(file), Ok(content)) = (check_file, serde_json::to_string(&check)) {
+        let _ = fs::write(file, content);
+    }
+    (check.compatible, true)
+}
+
+/// Choose the runtime for openclaw from those available, most preferred
+/// first. Bun is only used if it passes the compatibility check.
+fn choose_runtime(
+    preference: [JsRuntime; 2],
+    has_node: bool,
+    bun_works: impl Fn() -> bool,
+) -> Option<JsRuntime> {
+    preference.into_iter().find(|runtime| match runtime {
+        JsRuntime::Node => has_node,
+        JsRuntime::Bun => bun_works(),
+    })
+}
+
+/// The runtime openclaw runs on, chosen once per process: the configured one,
+/// otherwise Node.js, with Bun as the fallback when Node.js is missing
+pub fn selected() -> Option<JsRuntime> {
+    static RUNTIME: OnceLock<Option<JsRuntime>> = OnceLock::new();
+
+    *RUNTIME.get_or_init(|| {
+        let configured = crate::config::get().runtime;
+        let preference = match configured {
+            Some(JsRuntime::Bun) => [JsRuntime::Bun, JsRuntime::Node],
+            _ => [JsRuntime::Node, JsRuntime::Bun],
+        };
+        let bun = find_bun();
+        // Only explain the fallback when the check first fails, not on every run
+        let newly_failed = Cell::new(false);
+        let bun_works = || {
+            bun.as_deref().is_some_and(|bun| {
+                let (compatible, recorded) = bun_compatible(bun);
+                newly_failed.set(recorded && !compatible);
+                compatible
+            })
+        };
+        let runtime = choose_runtime(preference, detected_node().is_some(), bun_works);
+
+        if configured == Some(JsRuntime::Bun)
+            && runtime == Some(JsRuntime::Node)
+            && newly_failed.get()
+        {
+            eprintln!("chitin: bun can't run this openclaw; using Node.js");
+        }
+        runtime
+    })
+}
+
 impl RuntimeDetector {
     /// Create a new RuntimeDetector and probe for available runtimes
     pub fn new() -> Self {
         Self {
-            node_available: which::which("node").is_ok(),
+            node: detected_node().cloned(),
+            bun_path: find_bun(),
             npm_available: which::which("npm").is_ok(),
             pnpm_available: which::which("pnpm").is_ok(),
         }
@@ -19,7 +497,60 @@ impl RuntimeDetector {
 
     /// Check if Node.js is available
     pub fn has_node(&self) -> bool {
-        self.node_available
+        self.node.is_some()
+    }
+
+    /// The runtime openclaw runs on, if any can
+    pub fn runtime(&self) -> Option<JsRuntime> {
+        selected()
+    }
+
+    /// Check if a runtime that can run openclaw is available
+    pub fn has_runtime(&self) -> bool {
+        self.runtime().is_some()
+    }
+
+    /// Path of the bun binary, if installed
+    pub fn bun_path(&self) -> Option<&Path> {
+        self.bun_path.as_deref()
+    }
+
+    /// Get the version of bun, cached like [`Self::node_version`]
+    pub fn bun_version(&self) -> Option<Version> {
+        runtime_version(self.bun_path()?, BUN_VERSION_FILE)
+    }
+
+    /// Path of the node binary that runs openclaw
+    pub fn node_path(&self) -> Option<&Path> {
+        self.node.as_ref().map(|node| node.path.as_path())
+    }
+
+    /// The version manager node came from, if any
+    pub fn node_manager(&self) -> Option<NodeManager> {
+        self.node.as_ref()?.manager
+    }
+
+    /// Get the version of the node that runs openclaw, which may come from a
+    /// version manager rather than PATH. The result is cached until the node
+    /// binary changes, so this only starts Node.js after an upgrade.
+    pub fn node_version(&self) -> Option<Version> {
+        runtime_version(self.node_path()?, NODE_VERSION_FILE)
+    }
+
+    /// Check the node that runs openclaw against the installed openclaw's
+    /// `engines.node` range. Returns None when they match or either is unknown.
+    pub fn node_mismatch(&self) -> Option<NodeMismatch> {
+        let required = crate::openclaw::node_requirement()?;
+        let version = self.node_version()?;
+        if satisfies(version, required)? {
+            return None;
+        }
+        Some(NodeMismatch {
+            path: self.node_path()?.to_path_buf(),
+            manager: self.node_manager(),
+            version,
+            required: required.to_string(),
+        })
     }
 
     /// Check if npm is available
@@ -29,7 +560,6 @@ impl RuntimeDetector {
     }
 
     /// Check if pnpm is available
-    #[allow(dead_code)]
     pub fn has_pnpm(&self) -> bool {
         self.pnpm_available
     }
@@ -40,16 +570,22 @@ impl RuntimeDetector {
         self.npm_available || self.pnpm_available
     }
 
-    /// Get the preferred package manager (pnpm preferred)
-    #[allow(dead_code)]
-    pub fn preferred_package_manager(&self) -> Option<&'static str> {
-        if self.pnpm_available {
-            Some("pnpm")
-        } else if self.npm_available {
-            Some("npm")
-        } else {
-            None
-        }
+    /// Get the preferred available package manager: the configured one if
+    /// installed, otherwise pnpm, then npm
+    pub fn preferred_package_manager(&self) -> Option<PackageManager> {
+        self.choose_package_manager(crate::config::get().package_manager)
+    }
+
+    fn choose_package_manager(&self, configured: Option<PackageManager>) -> Option<PackageManager> {
+        let available = |pm: &PackageManager| match pm {
+            PackageManager::Pnpm => self.pnpm_available,
+            PackageManager::Npm => self.npm_available,
+        };
+
+        configured
+            .into_iter()
+            .chain([PackageManager::Pnpm, PackageManager::Npm])
+            .find(available)
     }
 }
 
@@ -73,4 +609,173 @@ mod tests {
         let _ = detector.has_package_manager();
         let _ = detector.preferred_package_manager();
     }
+
+    #[test]
+    fn test_configured_package_manager() {
+        let detector = RuntimeDetector {
+            node: Some(DetectedNode {
+                path: PathBuf::from("/usr/bin/node"),
+                manager: None,
+                on_path: true,
+            }),
+            bun_path: None,
+            npm_available: true,
+            pnpm_available: true,
+        };
+        assert_eq!(
+            detector.choose_package_manager(None),
+            Some(PackageManager::Pnpm)
+        );
+        assert_eq!(
+            detector.choose_package_manager(Some(PackageManager::Npm)),
+            Some(PackageManager::Npm)
+        );
+
+        // A configured manager that isn't installed falls back to what is
+        let detector = RuntimeDetector {
+            pnpm_available: false,
+            ..detector
+        };
+        assert_eq!(
+            detector.choose_package_manager(Some(PackageManager::Pnpm)),
+            Some(PackageManager::Npm)
+        );
+    }
+
+    fn version(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_node_version() {
+        assert_eq!(
+            Version::parse("v22.12.0\n"),
+            Some(Version {
+                major: 22,
+                minor: 12,
+                patch: 0
+            })
+        );
+        assert_eq!(version("v22.12.0").to_string(), "v22.12.0");
+        assert_eq!(Version::parse("v22.12"), None);
+        assert_eq!(Version::parse("node"), None);
+    }
+
+    #[test]
+    fn test_satisfies() {
+        let cases = [
+            (">=22.12.0", "v22.12.0", true),
+            (">=22.12.0", "v22.11.9", false),
+            (">=22.12.0", "v18.19.0", false),
+            (">= 22", "v22.0.0", true),
+            (">22", "v22.99.0", false),
+            (">22", "v23.0.0", true),
+            ("<23", "v22.99.0", true),
+            ("<=22.12", "v22.12.9", true),
+            ("<=22.12", "v22.13.0", false),
+            ("^20.19.0 || >=22.12.0", "v20.20.2", true),
+            ("^20.19.0 || >=22.12.0", "v21.0.0", false),
+            ("^20.19.0 || >=22.12.0", "v24.1.0", true),
+            ("~22.12.1", "v22.12.5", true),
+            ("~22.12.1", "v22.13.0", false),
+            ("^0.10.2", "v0.11.0", false),
+            ("22.x", "v22.1.0", true),
+            ("22", "v23.0.0", false),
+            ("20 - 22.12", "v22.12.3", true),
+            ("20 - 22.12", "v22.13.0", false),
+            (">=18 <23", "v23.0.0", false),
+            ("*", "v4.0.0", true),
+        ];
+        for (range, v, expected) in cases {
+            assert_eq!(
+                satisfies(version(v), range),
+                Some(expected),
+                "{} {}",
+                range,
+                v
+            );
+        }
+        assert_eq!(satisfies(version("v22.0.0"), ">=latest"), None);
+    }
+
+    #[test]
+    fn test_upgrade_major() {
+        let mismatch = |required: &str, v: &str| NodeMismatch {
+            path: PathBuf::from("/usr/bin/node"),
+            manager: None,
+            version: version(v),
+            required: required.to_string(),
+        };
+        assert_eq!(mismatch(">=22.12.0", "v18.19.0").upgrade_major(), Some(22));
+        assert_eq!(
+            mismatch("^20.19.0 || >=22.12.0", "v18.19.0").upgrade_major(),
+            Some(20)
+        );
+        assert_eq!(mismatch("<18", "v20.0.0").upgrade_major(), None);
+        assert_eq!(
+            mismatch(">=22.12.0", "v18.19.0").to_string(),
+            "openclaw requires Node.js >=22.12.0, but /usr/bin/node is v18.19.0"
+        );
+        let nvm = NodeMismatch {
+            path: PathBuf::from("/home/me/.nvm/versions/node/v18.19.0/bin/node"),
+            manager: Some(NodeManager::Nvm),
+            ..mismatch(">=22.12.0", "v18.19.0")
+        };
+        assert_eq!(
+            nvm.to_string(),
+            "openclaw requires Node.js >=22.12.0, but \
+             /home/me/.nvm/versions/node/v18.19.0/bin/node (from nvm) is v18.19.0"
+        );
+    }
+
+    #[test]
+    fn test_choose_runtime() {
+        use JsRuntime::{Bun, Node};
+
+        assert_eq!(choose_runtime([Node, Bun], true, || true), Some(Node));
+        assert_eq!(choose_runtime([Bun, Node], true, || true), Some(Bun));
+        // Bun failing its check falls back to Node.js, and vice versa
+        assert_eq!(choose_runtime([Bun, Node], true, || false), Some(Node));
+        assert_eq!(choose_runtime([Node, Bun], false, || true), Some(Bun));
+        assert_eq!(choose_runtime([Node, Bun], false, || false), None);
+
+        assert_eq!(JsRuntime::from_name(" Bun ").unwrap(), Bun);
+        assert_eq!(JsRuntime::from_name("nodejs").unwrap(), Node);
+        assert!(JsRuntime::from_name("deno").is_err());
+        assert_eq!(Version::parse("1.1.38\n"), Some(version("v1.1.38")));
+    }
+
+    #[test]
+    fn test_cached_version() {
+        let dir = std::env::temp_dir().join(format!("chitin-node-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let node = dir.join("node");
+        let cache_file = dir.join(NODE_VERSION_FILE);
+        fs::write(&node, "v20").unwrap();
+
+        let probes = std::cell::Cell::new(0);
+        let probe = |_: &Path| {
+            probes.set(probes.get() + 1);
+            Version::parse("v20.20.2")
+        };
+        assert_eq!(
+            cached_version(Some(&cache_file), &node, probe),
+            Some(version("v20.20.2"))
+        );
+        assert_eq!(
+            cached_version(Some(&cache_file), &node, probe),
+            Some(version("v20.20.2"))
+        );
+        assert_eq!(probes.get(), 1);
+
+        // A replaced binary is probed again
+        fs::write(&node, "v22.12.0").unwrap();
+        assert_eq!(
+            cached_version(Some(&cache_file), &node, |_| Version::parse("v22.12.0")),
+            Some(version("v22.12.0"))
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,470 @@
+//! Native `sessions` - lists the sessions in openclaw's session store
+//! (`<state dir>/agents/<agent>/sessions/sessions.json`) without starting
+//! Node.js, with the same flags and `--json` output as openclaw.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::native::{Flag, NativeCommand, Outcome};
+use crate::openclaw_config;
+
+/// Agent whose store is listed when the config names none
+const DEFAULT_AGENT: &str = "main";
+
+/// A session as openclaw's `sessions --json` prints it, field for field.
+/// Fields copied from the store entry keep their raw JSON and are left out
+/// when the entry doesn't have them.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    key: String,
+    /// "direct", "group", "global" or "unknown"
+    kind: &'static str,
+    /// Last activity (Unix epoch milliseconds)
+    updated_at: Option<u64>,
+    age_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_sent: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aborted_last_run: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_level: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verbose_level: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_level: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elevated_level: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_usage: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group_activation: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input_tokens: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_tokens: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_tokens: Option<Value>,
+    model: Value,
+    context_tokens: Value,
+}
+
+impl Session {
+    /// Total tokens, or input plus output when the store has no total
+    fn tokens(&self) -> Option<u64> {
+        let number = |value: &Option<Value>| value.as_ref().and_then(Value::as_u64);
+        number(&self.total_tokens).or_else(|| {
+            let input = number(&self.input_tokens)?;
+            Some(input + number(&self.output_tokens).unwrap_or(0))
+        })
+    }
+}
+
+/// `sessions --json` output, in openclaw's field order
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Listing<'a> {
+    path: &'a Path,
+    count: usize,
+    active_minutes: Option<u64>,
+    sessions: &'a [Session],
+}
+
+/// openclaw's session kind, from the entry's chat type or the key
+fn kind(key: &str, entry: &Map<String, Value>) -> &'static str {
+    let chat_type = entry.get("chatType").and_then(Value::as_str);
+    match key {
+        "global" => "global",
+        "unknown" => "unknown",
+        _ if matches!(chat_type, Some("group" | "channel")) => "group",
+        _ if key.contains(":group:") || key.contains(":channel:") => "group",
+        _ => "direct",
+    }
+}
+
+/// Parse a session store. Returns None if it doesn't look like a session
+/// store this version of chitin understands, or if a session has no model or
+/// context size of its own: openclaw fills those in from its model catalog.
+fn parse_store(content: &str, now_ms: u64) -> Option<Vec<Session>> {
+    let store: Value = serde_json::from_str(content).ok()?;
+    let mut sessions = Vec::new();
+
+    for (key, entry) in store.as_object()? {
+        let entry = entry.as_object()?;
+        let field = |name: &str| entry.get(name).cloned();
+        let updated_at = match entry.get("updatedAt") {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(value.as_u64()?),
+        };
+        let known = |name: &str| field(name).filter(|value| !value.is_null());
+
+        sessions.push(Session {
+            key: key.clone(),
+            kind: kind(key, entry),
+            updated_at,
+            age_ms: updated_at
+                .filter(|&at| at > 0)
+                .map(|at| now_ms as i64 - at as i64),
+            session_id: field("sessionId"),
+            system_sent: field("systemSent"),
+            aborted_last_run: field("abortedLastRun"),
+            thinking_level: field("thinkingLevel"),
+            verbose_level: field("verboseLevel"),
+            reasoning_level: field("reasoningLevel"),
+            elevated_level: field("elevatedLevel"),
+            response_usage: field("responseUsage"),
+            group_activation: field("groupActivation"),
+            input_tokens: field("inputTokens"),
+            output_tokens: field("outputTokens"),
+            total_tokens: field("totalTokens"),
+            model: known("model")?,
+            context_tokens: known("contextTokens")?,
+        });
+    }
+
+    Some(sessions)
+}
+
+/// Default agent: the one marked `default` in `agents.list`, else the first
+/// listed, else "main"
+fn default_agent(config: Option<&Value>) -> String {
+    let agents = config
+        .and_then(|c| c.pointer("/agents/list"))
+        .and_then(Value::as_array);
+    let id = |agent: &Value| agent.get("id").and_then(Value::as_str).map(String::from);
+    agents
+        .and_then(|list| {
+            list.iter()
+                .find(|agent| agent.get("default").and_then(Value::as_bool) == Some(true))
+                .or_else(|| list.first())
+                .and_then(id)
+        })
+        .unwrap_or_else(|| DEFAULT_AGENT.to_string())
+}
+
+/// The store openclaw lists: `--store`, then `session.store` from the config
+/// (with `{agentId}` and `~` expanded), then the default agent's store
+fn store_path(store: Option<&str>, config: Option<&Value>, state_dir: &Path) -> Option<PathBuf> {
+    let agent = default_agent(config);
+    let configured = config
+        .and_then(|c| c.pointer("/session/store"))
+        .and_then(Value::as_str);
+    let Some(store) = store.or(configured).filter(|s| !s.is_empty()) else {
+        return Some(
+            state_dir
+                .join("agents")
+                .join(agent)
+                .join("sessions")
+                .join("sessions.json"),
+        );
+    };
+
+    let store = store.replace("{agentId}", &agent);
+    let path = match store.strip_prefix('~') {
+        Some(rest) => dirs::home_dir()?.join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(store),
+    };
+    std::path::absolute(path).ok()
+}
+
+#[derive(Debug)]
+struct Options {
+    json: bool,
+    /// Session store to read instead of the configured one
+    store: Option<String>,
+    /// Only sessions active within this many milliseconds
+    active_ms: Option<u64>,
+    /// `--active` as given, echoed in the output
+    active_minutes: Option<u64>,
+}
+
+fn parse_args(args: &[String]) -> Option<Options> {
+    let mut options = Options {
+        json: false,
+        store: None,
+        active_ms: None,
+        active_minutes: None,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--store" => options.store = Some(args.next()?.clone()),
+            // openclaw reports non-positive and out-of-range values itself
+            "--active" => {
+                let minutes: u64 = args.next()?.parse().ok().filter(|&m| m > 0)?;
+                options.active_ms = Some(minutes.checked_mul(60_000)?);
+                options.active_minutes = Some(minutes);
+            }
+            _ => return None,
+        }
+    }
+    Some(options)
+}
+
+/// Drop sessions outside `--active` and sort newest first, like openclaw
+fn select(mut sessions: Vec<Session>, options: &Options, now_ms: u64) -> Vec<Session> {
+    if let Some(active_ms) = options.active_ms {
+        sessions.retain(|s| {
+            s.updated_at
+                .is_some_and(|at| at > 0 && now_ms.saturating_sub(at) <= active_ms)
+        });
+    }
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at.unwrap_or(0)));
+    sessions
+}
+
+fn print_table(sessions: &[Session], now_ms: u64) {
+    if sessions.is_empty() {
+        println!("No sessions found.");
+        return;
+    }
+
+    let rows: Vec<[String; 5]> = sessions
+        .iter()
+        .map(|s| {
+            let context = s.context_tokens.as_u64();
+            let tokens = match (s.tokens(), context) {
+                (Some(used), Some(context)) if context > 0 => {
+                    format!("{}/{} ({}%)", used, context, used * 100 / context)
+                }
+                (Some(used), _) => used.to_string(),
+                (None, _) => "-".to_string(),
+            };
+            [
+                s.kind.to_string(),
+                s.key.clone(),
+                s.updated_at.map_or_else(
+                    || "-".to_string(),
+                    |at| crate::cache::format_age(now_ms.saturating_sub(at) / 1000),
+                ),
+                s.model.as_str().unwrap_or("-").to_string(),
+                tokens,
+            ]
+        })
+        .collect();
+
+    let header = ["Kind", "Key", "Age", "Model", "Tokens (ctx %)"];
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: &[&str]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    };
+    print_row(&header);
+    for row in &rows {
+        print_row(&row.each_ref().map(String::as_str));
+    }
+}
+
+/// Flags the native handler supports, the same as openclaw's
+const FLAGS: &[Flag] = &[
+    Flag::switch("--json"),
+    Flag::value("--store"),
+    Flag::value("--active"),
+];
+
+/// `chitin sessions [--json] [--store <path>] [--active <minutes>]`. Session
+/// storage chitin doesn't recognize is delegated.
+pub struct SessionsCommand;
+
+impl NativeCommand for SessionsCommand {
+    fn path(&self) -> &'static [&'static str] {
+        &["sessions"]
+    }
+
+    fn flags(&self) -> &'static [Flag] {
+        FLAGS
+    }
+
+    fn run(&self, args: &[String]) -> Result<Outcome> {
+        match parse_args(args) {
+            Some(options) => list(&options),
+            None => Ok(Outcome::Delegate),
+        }
+    }
+}
+
+fn list(options: &Options) -> Result<Outcome> {
+    // openclaw reports unreadable configs and stores in its own words
+    let Ok(config) = openclaw_config::load() else {
+        return Ok(Outcome::Delegate);
+    };
+    let Some(path) = store_path(
+        options.store.as_deref(),
+        config.as_ref(),
+        &openclaw_config::state_dir()?,
+    ) else {
+        return Ok(Outcome::Delegate);
+    };
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        // A store that doesn't exist yet has no sessions
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => "{}".to_string(),
+        Err(_) => return Ok(Outcome::Delegate),
+    };
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    let Some(sessions) = parse_store(&content, now_ms) else {
+        return Ok(Outcome::Delegate);
+    };
+    let sessions = select(sessions, options, now_ms);
+
+    if options.json {
+        let listing = Listing {
+            path: &path,
+            count: sessions.len(),
+            active_minutes: options.active_minutes,
+            sessions: &sessions,
+        };
+        println!("{}", serde_json::to_string_pretty(&listing)?);
+    } else {
+        println!("Session store: {}", path.display());
+        println!("Sessions listed: {}", sessions.len());
+        if let Some(minutes) = options.active_minutes {
+            println!("Filtered to last {} minute(s)", minutes);
+        }
+        print_table(&sessions, now_ms);
+    }
+    Ok(Outcome::Handled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const STORE: &str = r#"{
+        "agent:main:main": {"sessionId": "a1", "updatedAt": 1000000, "model": "gpt-5", "contextTokens": 400000, "totalTokens": 1200},
+        "agent:main:whatsapp:dm:+15555550123": {"sessionId": "b2", "updatedAt": 3000000, "model": "gpt-5", "contextTokens": 400000, "inputTokens": 10, "outputTokens": 5},
+        "agent:main:telegram:group:-100123": {"sessionId": "c3", "updatedAt": 2000000, "model": "gpt-5", "contextTokens": 400000, "thinkingLevel": "high"}
+    }"#;
+
+    fn options(args: &[&str]) -> Options {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        parse_args(&args).unwrap()
+    }
+
+    fn keys(sessions: &[Session]) -> Vec<&str> {
+        sessions.iter().map(|s| s.key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_store() {
+        let sessions = parse_store(STORE, 3_000_000).unwrap();
+        assert_eq!(sessions.len(), 3);
+
+        let dm = sessions.iter().find(|s| s.key.contains(":dm:")).unwrap();
+        assert_eq!(dm.kind, "direct");
+        assert_eq!(dm.age_ms, Some(0));
+        assert_eq!(dm.tokens(), Some(15));
+
+        let group = sessions.iter().find(|s| s.key.contains(":group:")).unwrap();
+        assert_eq!(group.kind, "group");
+        assert_eq!(group.age_ms, Some(1_000_000));
+    }
+
+    #[test]
+    fn test_json_matches_openclaw() {
+        let sessions = parse_store(STORE, 3_000_000).unwrap();
+        let group = sessions.iter().find(|s| s.key.contains(":group:")).unwrap();
+        assert_eq!(
+            serde_json::to_value(group).unwrap(),
+            json!({
+                "key": "agent:main:telegram:group:-100123",
+                "kind": "group",
+                "updatedAt": 2000000,
+                "ageMs": 1000000,
+                "sessionId": "c3",
+                "thinkingLevel": "high",
+                "model": "gpt-5",
+                "contextTokens": 400000
+            })
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_store() {
+        assert!(parse_store("[]", 0).is_none());
+        assert!(parse_store(r#"{"agent:main:main": "x"}"#, 0).is_none());
+        assert!(parse_store(r#"{"agent:main:main": {"updatedAt": "soon"}}"#, 0).is_none());
+        // Model and context size would come from openclaw's defaults
+        assert!(parse_store(r#"{"agent:main:main": {"contextTokens": 1}}"#, 0).is_none());
+        assert_eq!(parse_store("{}", 0), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_select() {
+        let now = 3_000_000;
+        let sessions = parse_store(STORE, now).unwrap();
+
+        let all = select(sessions.clone(), &options(&[]), now);
+        assert_eq!(
+            keys(&all),
+            vec![
+                "agent:main:whatsapp:dm:+15555550123",
+                "agent:main:telegram:group:-100123",
+                "agent:main:main"
+            ]
+        );
+
+        // Active within the last 20 minutes (1,200,000 ms)
+        let active = select(sessions, &options(&["--active", "20"]), now);
+        assert_eq!(active.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = options(&["--active", "60", "--store", "s.json", "--json"]);
+        assert_eq!(parsed.active_ms, Some(3_600_000));
+        assert_eq!(parsed.store.as_deref(), Some("s.json"));
+
+        let parse = |args: &[&str]| {
+            parse_args(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>()).is_none()
+        };
+        assert!(parse(&["--verbose"]));
+        assert!(parse(&["--agent", "main"]));
+        assert!(parse(&["--active", "soon"]));
+        assert!(parse(&["--active", "0"]));
+        assert!(parse(&["--active", "99999999999999999"]));
+    }
+
+    #[test]
+    fn test_store_path() {
+        let state = Path::new("/state");
+        assert_eq!(
+            store_path(None, None, state),
+            Some(state.join("agents/main/sessions/sessions.json"))
+        );
+
+        let config = json!({
+            "agents": {"list": [{"id": "home"}, {"id": "work", "default": true}]},
+            "session": {"store": "/data/{agentId}/sessions.json"}
+        });
+        assert_eq!(
+            store_path(None, Some(&config), state),
+            Some(PathBuf::from("/data/work/sessions.json"))
+        );
+        assert_eq!(
+            store_path(Some("/tmp/s.json"), Some(&config), state),
+            Some(PathBuf::from("/tmp/s.json"))
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Shim mode - chitin installed as `openclaw` (a symlink ahead of the npm/pnpm
+//! shim on PATH) behaves as a drop-in replacement, keeping openclaw's branding
+//! and running the real Node.js CLI for anything it doesn't answer itself.
+
+use anyhow::{Context, Result};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Name chitin answers to in shim mode
+const SHIM_NAME: &str = "openclaw";
+
+const USAGE: &str = "Usage: chitin shim <install|uninstall> [--dir <bin directory>]";
+
+/// Whether chitin was invoked as `openclaw`
+pub fn active() -> bool {
+    static ACTIVE: OnceLock<bool> = OnceLock::new();
+
+    *ACTIVE.get_or_init(|| {
+        std::env::args_os()
+            .next()
+            .is_some_and(|arg0| is_shim_name(Path::new(&arg0)))
+    })
+}
+
+/// Whether argv[0] names the shim (`openclaw`, or `openclaw.exe` on Windows)
+fn is_shim_name(arg0: &Path) -> bool {
+    arg0.file_stem().is_some_and(|stem| stem == SHIM_NAME)
+}
+
+/// Default directory for the shim link
+fn default_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Cannot find home directory")?;
+    Ok(home.join(".chitin").join("bin"))
+}
+
+/// Whether `dir` is on PATH ahead of `other` (or at all, if `other` isn't on it)
+fn ahead(path_var: &OsStr, dir: &Path, other: Option<&Path>) -> bool {
+    let dirs: Vec<PathBuf> = std::env::split_paths(path_var).collect();
+    let Some(ours) = dirs.iter().position(|d| d == dir) else {
+        return false;
+    };
+    match other.and_then(|other| dirs.iter().position(|d| d == other)) {
+        Some(theirs) => ours < theirs,
+        None => true,
+    }
+}
+
+fn install(dir: &Path, link: &Path) -> Result<()> {
+    if link.symlink_metadata().is_ok() {
+        if !crate::openclaw::is_self(link) {
+            anyhow::bail!(
+                "{} already exists and is not chitin; remove it or choose another --dir",
+                link.display()
+            );
+        }
+        println!("{} already runs chitin", link.display());
+    } else {
+        let exe = std::env::current_exe()
+            .and_then(fs::canonicalize)
+            .context("Cannot locate the chitin binary")?;
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        #[cfg(unix)]
+        let result = std::os::unix::fs::symlink(&exe, link);
+        #[cfg(windows)]
+        let result = std::os::windows::fs::symlink_file(&exe, link);
+        result.with_context(|| format!("Failed to create {}", link.display()))?;
+
+        println!("Linked {} -> {}", link.display(), exe.display());
+    }
+
+    // The real openclaw, which the shim has to shadow on PATH
+    let real = crate::openclaw::find_shim();
+    let real_dir = real.as_deref().and_then(Path::parent);
+    if !ahead(&std::env::var_os("PATH").unwrap_or_default(), dir, real_dir) {
+        println!();
+        match real_dir {
+            Some(real_dir) => println!(
+                "Put {} ahead of {} on PATH so `openclaw` runs chitin:",
+                dir.display(),
+                real_dir.display()
+            ),
+            None => println!("Add {} to PATH so `openclaw` runs chitin:", dir.display()),
+        }
+        println!("  export PATH=\"{}:$PATH\"", dir.display());
+    }
+    Ok(())
+}
+
+fn uninstall(link: &Path) -> Result<()> {
+    if link.symlink_metadata().is_err() {
+        println!("No chitin shim at {}", link.display());
+        return Ok(());
+    }
+    if !crate::openclaw::is_self(link) {
+        anyhow::bail!(
+            "{} is not a chitin shim; leaving it in place",
+            link.display()
+        );
+    }
+    fs::remove_file(link).with_context(|| format!("Failed to remove {}", link.display()))?;
+    println!("Removed {}", link.display());
+    Ok(())
+}
+
+/// Handle `chitin shim <install|uninstall> [--dir <bin directory>]`
+pub fn command(args: &[String]) -> Result<()> {
+    let (action, dir) = match args {
+        [action] => (action.as_str(), default_dir()?),
+        [action, flag, dir] if flag == "--dir" => (action.as_str(), PathBuf::from(dir)),
+        _ => anyhow::bail!(USAGE),
+    };
+    let link = dir.join(SHIM_NAME);
+
+    match action {
+        "install" => install(&dir, &link),
+        "uninstall" => uninstall(&link),
+        _ => anyhow::bail!(USAGE),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_shim_name() {
+        assert!(is_shim_name(Path::new("openclaw")));
+        assert!(is_shim_name(Path::new("/home/me/.chitin/bin/openclaw")));
+        assert!(is_shim_name(Path::new("openclaw.exe")));
+        assert!(!is_shim_name(Path::new("chitin")));
+        assert!(!is_shim_name(Path::new("/usr/bin/openclaw-dev")));
+    }
+
+    #[test]
+    fn test_ahead() {
+        let path_var = std::env::join_paths(["/home/me/.chitin/bin", "/usr/bin"]).unwrap();
+        let dir = Path::new("/home/me/.chitin/bin");
+        assert!(ahead(&path_var, dir, Some(Path::new("/usr/bin"))));
+        assert!(ahead(&path_var, dir, None));
+        assert!(!ahead(&path_var, Path::new("/usr/bin"), Some(dir)));
+        assert!(!ahead(&path_var, Path::new("/opt/bin"), None));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_uninstall() {
+        let dir = std::env::temp_dir().join(format!("chitin-shim-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let link = dir.join(SHIM_NAME);
+
+        install(&dir, &link).unwrap();
+        assert!(crate::openclaw::is_self(&link));
+        // Installing again is a no-op
+        install(&dir, &link).unwrap();
+
+        uninstall(&link).unwrap();
+        assert!(link.symlink_metadata().is_err());
+        uninstall(&link).unwrap();
+
+        // Never replaces or removes a real openclaw
+        fs::write(&link, "#!/bin/sh\n").unwrap();
+        assert!(install(&dir, &link).is_err());
+        assert!(uninstall(&link).is_err());
+        assert!(link.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,383 @@
+//! Native `status` - a quick health check of the openclaw installation, Node.js,
+//! the openclaw config and the local gateway, without starting Node.js for
+//! openclaw itself.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::native::{Flag, NativeCommand, Outcome};
+use crate::node_managers::NodeManager;
+use crate::openclaw_config;
+use crate::runtime::{self, JsRuntime, RuntimeDetector, Version};
+
+/// How long to wait for the gateway to accept a connection
+const GATEWAY_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Serialize)]
+struct Status {
+    openclaw: Installation,
+    node: Node,
+    bun: Bun,
+    /// Runtime openclaw runs on
+    runtime: Option<JsRuntime>,
+    package_manager: Option<&'static str>,
+    config: ConfigFile,
+    gateway: Gateway,
+    /// Human-readable descriptions of everything that needs attention
+    problems: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Installation {
+    /// openclaw.mjs entry point
+    entry: Option<PathBuf>,
+    /// `openclaw` shim on PATH
+    shim: Option<PathBuf>,
+    version: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct Node {
+    path: Option<PathBuf>,
+    /// Version manager that supplied node, when it isn't a system install
+    manager: Option<NodeManager>,
+    version: Option<String>,
+    /// Versions openclaw supports (its `engines.node` range)
+    required: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct Bun {
+    path: Option<PathBuf>,
+    version: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ConfigFile {
+    path: Option<PathBuf>,
+    exists: bool,
+    /// Parse error, if the file is invalid
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Gateway {
+    /// "local" or "remote" (`gateway.mode`)
+    mode: String,
+    port: u16,
+    /// Whether the local gateway accepted a connection (None when not probed)
+    reachable: Option<bool>,
+    latency_ms: Option<u64>,
+}
+
+/// Check whether something accepts TCP connections on a local port, returning
+/// the connect time
+pub fn probe_port(port: u16, timeout: Duration) -> Option<Duration> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let start = Instant::now();
+    TcpStream::connect_timeout(&addr, timeout)
+        .ok()
+        .map(|_| start.elapsed())
+}
+
+fn collect() -> Status {
+    let openclaw = Installation {
+        entry: crate::openclaw::find_entry().ok(),
+        shim: crate::openclaw::find_shim(),
+        version: crate::openclaw::installed_version(),
+    };
+
+    let detector = RuntimeDetector::new();
+    let node = Node {
+        path: detector.node_path().map(PathBuf::from),
+        manager: detector.node_manager(),
+        version: detector.node_version().map(|v| v.to_string()),
+        required: crate::openclaw::node_requirement(),
+    };
+    let bun = Bun {
+        path: detector.bun_path().map(PathBuf::from),
+        version: detector.bun_version().map(|v| v.to_string()),
+    };
+
+    let (config_value, config) = match openclaw_config::config_path() {
+        Ok(path) => {
+            let exists = path.exists();
+            let (value, error) = match openclaw_config::load() {
+                Ok(value) => (value, None),
+                Err(e) => (None, Some(format!("{:#}", e))),
+            };
+            (
+                value,
+                ConfigFile {
+                    path: Some(path),
+                    exists,
+                    error,
+                },
+            )
+        }
+        Err(e) => (
+            None,
+            ConfigFile {
+                path: None,
+                exists: false,
+                error: Some(e.to_string()),
+            },
+        ),
+    };
+
+    let mode = config_value
+        .as_ref()
+        .and_then(|c| c.pointer("/gateway/mode"))
+        .and_then(Value::as_str)
+        .unwrap_or("local")
+        .to_string();
+    let port = openclaw_config::gateway_port(config_value.as_ref());
+    // A remote gateway isn't expected to listen locally
+    let latency = (mode != "remote").then(|| probe_port(port, GATEWAY_TIMEOUT));
+    let gateway = Gateway {
+        mode,
+        port,
+        reachable: latency.map(|l| l.is_some()),
+        latency_ms: latency.flatten().map(|l| l.as_millis() as u64),
+    };
+
+    let mut status = Status {
+        openclaw,
+        node,
+        bun,
+        runtime: detector.runtime(),
+        package_manager: detector.preferred_package_manager().map(|pm| pm.name()),
+        config,
+        gateway,
+        problems: Vec::new(),
+    };
+    status.problems = problems(&status);
+    status
+}
+
+fn problems(status: &Status) -> Vec<String> {
+    let mut problems = Vec::new();
+    if status.openclaw.entry.is_none() && status.openclaw.shim.is_none() {
+        problems.push("openclaw is not installed".to_string());
+    }
+    if status.runtime == Some(JsRuntime::Bun) {
+        // Node.js doesn't matter while openclaw runs on Bun
+    } else if status.node.path.is_none() {
+        problems.push("Node.js is not installed".to_string());
+    } else if let Some(version) = &status.node.version {
+        if let (Some(required), Some(v)) = (status.node.required, Version::parse(version))
+            && runtime::satisfies(v, required) == Some(false)
+        {
+            problems.push(format!(
+                "Node.js {} is not supported (openclaw requires {})",
+                version, required
+            ));
+        }
+    } else {
+        problems.push("Node.js does not run".to_string());
+    }
+    if let Some(error) = &status.config.error {
+        problems.push(format!("config is invalid: {}", error));
+    } else if !status.config.exists {
+        problems.push("openclaw is not configured (run 'chitin onboard')".to_string());
+    }
+    if status.gateway.reachable == Some(false) {
+        problems.push(format!(
+            "gateway is not responding on port {}",
+            status.gateway.port
+        ));
+    }
+    problems
+}
+
+fn display(path: &Option<PathBuf>) -> String {
+    path.as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "not found".to_string())
+}
+
+fn print(status: &Status) {
+    println!("chitin status");
+    println!(
+        "  openclaw:        {}",
+        status.openclaw.version.unwrap_or("not found")
+    );
+    println!("  entry:           {}", display(&status.openclaw.entry));
+    println!("  shim:            {}", display(&status.openclaw.shim));
+    let via = status
+        .node
+        .manager
+        .map(|m| format!(", via {}", m.name()))
+        .unwrap_or_default();
+    println!(
+        "  node:            {} ({}{})",
+        status.node.version.as_deref().unwrap_or("not found"),
+        display(&status.node.path),
+        via
+    );
+    if let Some(path) = &status.bun.path {
+        println!(
+            "  bun:             {} ({})",
+            status.bun.version.as_deref().unwrap_or("unknown"),
+            path.display()
+        );
+    }
+    println!(
+        "  runtime:         {}",
+        status.runtime.map_or("none", |r| r.name())
+    );
+    println!(
+        "  package manager: {}",
+        status.package_manager.unwrap_or("none")
+    );
+    let config_state = if status.config.error.is_some() {
+        "invalid"
+    } else if status.config.exists {
+        "ok"
+    } else {
+        "missing"
+    };
+    println!(
+        "  config:          {} ({})",
+        display(&status.config.path),
+        config_state
+    );
+    let gateway = match (status.gateway.reachable, status.gateway.latency_ms) {
+        (None, _) => "remote mode, not probed".to_string(),
+        (Some(true), Some(ms)) => format!("responding on port {} ({}ms)", status.gateway.port, ms),
+        _ => format!("not responding on port {}", status.gateway.port),
+    };
+    println!("  gateway:         {}", gateway);
+
+    if !status.problems.is_empty() {
+        println!();
+        println!("Problems:");
+        for problem in &status.problems {
+            println!("  - {}", problem);
+        }
+    }
+}
+
+/// Flags the native handler supports
+const FLAGS: &[Flag] = &[Flag::switch("--json")];
+
+/// `chitin status [--json]`, exiting with 1 if there are problems
+pub struct StatusCommand;
+
+impl NativeCommand for StatusCommand {
+    fn path(&self) -> &'static [&'static str] {
+        &["status"]
+    }
+
+    fn flags(&self) -> &'static [Flag] {
+        FLAGS
+    }
+
+    fn run(&self, args: &[String]) -> Result<Outcome> {
+        // `status` takes no positional arguments
+        if args.iter().any(|a| a != "--json") {
+            return Ok(Outcome::Delegate);
+        }
+        let json = !args.is_empty();
+
+        let status = collect();
+        if json {
+            println!("{}", serde_json::to_string_pretty(&status)?);
+        } else {
+            print(&status);
+        }
+
+        if !status.problems.is_empty() {
+            std::process::exit(1);
+        }
+        Ok(Outcome::Handled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn healthy() -> Status {
+        Status {
+            openclaw: Installation {
+                entry: Some(PathBuf::from("/usr/lib/node_modules/openclaw/openclaw.mjs")),
+                shim: Some(PathBuf::from("/usr/bin/openclaw")),
+                version: Some("2026.6.1"),
+            },
+            node: Node {
+                path: Some(PathBuf::from("/usr/bin/node")),
+                manager: None,
+                version: Some("v22.12.0".to_string()),
+                required: Some(">=22.12.0"),
+            },
+            bun: Bun {
+                path: None,
+                version: None,
+            },
+            runtime: Some(JsRuntime::Node),
+            package_manager: Some("npm"),
+            config: ConfigFile {
+                path: Some(PathBuf::from("/root/.openclaw/openclaw.json")),
+                exists: true,
+                error: None,
+            },
+            gateway: Gateway {
+                mode: "local".to_string(),
+                port: 18789,
+                reachable: Some(true),
+                latency_ms: Some(1),
+            },
+            problems: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_problems() {
+        assert!(problems(&healthy()).is_empty());
+
+        let mut status = healthy();
+        status.node.path = None;
+        status.gateway.reachable = Some(false);
+        assert_eq!(
+            problems(&status),
+            vec![
+                "Node.js is not installed",
+                "gateway is not responding on port 18789"
+            ]
+        );
+
+        let mut status = healthy();
+        status.node.version = Some("v20.20.2".to_string());
+        assert_eq!(
+            problems(&status),
+            vec!["Node.js v20.20.2 is not supported (openclaw requires >=22.12.0)"]
+        );
+
+        // Node.js isn't needed while openclaw runs on Bun
+        let mut status = healthy();
+        status.node.path = None;
+        status.runtime = Some(JsRuntime::Bun);
+        assert!(problems(&status).is_empty());
+
+        // A remote gateway is not probed and not a problem
+        let mut status = healthy();
+        status.gateway.reachable = None;
+        assert!(problems(&status).is_empty());
+    }
+
+    #[test]
+    fn test_probe_port() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(probe_port(port, GATEWAY_TIMEOUT).is_some());
+
+        drop(listener);
+        assert!(probe_port(port, GATEWAY_TIMEOUT).is_none());
+    }
+}